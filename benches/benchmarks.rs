@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
-use rolling_dual_crc::{DualCrc, RollingDualCrc, Zeros};
+use rolling_dual_crc::{DualCrc, MultiRollingDualCrc, RollingDualCrc, Zeros};
 
 fn benchmarks(c: &mut Criterion) {
     // ============================================================
@@ -66,6 +66,53 @@ fn benchmarks(c: &mut Criterion) {
 
     group.finish();
 
+    // ============================================================
+    // ROLL SLICE
+
+    let mut group = c.benchmark_group("roll_slice");
+    for size in [8, 16, 32, 64, 256, 2048] {
+        let data = vec![b'x'; size];
+
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("roll_slice 1 kiB", size),
+            &data,
+            |b, data| b.iter(|| crc_1k.roll_slice(black_box(data))),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("roll_slice_final 1 kiB", size),
+            &data,
+            |b, data| b.iter(|| crc_1k.roll_slice_final(black_box(data))),
+        );
+    }
+    group.finish();
+
+    // ============================================================
+    // MULTI
+
+    let bytes_4k = vec![b'x'; 4096];
+    let mut crc_48 = RollingDualCrc::new(&bytes_4k[..48]);
+    let mut crc_4k = RollingDualCrc::new(&bytes_4k);
+    let mut crc_multi = MultiRollingDualCrc::new(&[48, 4096], &bytes_4k);
+
+    let mut group = c.benchmark_group("multi");
+    group.throughput(Throughput::Bytes(1));
+
+    group.bench_function("RollingDualCrc::roll 48 B + 4 kiB", |b| {
+        b.iter(|| {
+            crc_48.roll(black_box(b'x'));
+            crc_4k.roll(black_box(b'x'));
+        })
+    });
+
+    group.bench_function("MultiRollingDualCrc::roll 48 B + 4 kiB", |b| {
+        b.iter(|| crc_multi.roll(black_box(b'x')))
+    });
+
+    group.finish();
+
     // ============================================================
     // ZEROS
 
@@ -92,7 +139,7 @@ fn benchmarks(c: &mut Criterion) {
             &zeros,
             |b, zeros| {
                 b.iter(|| {
-                    crc.update_with_zeros(&zeros);
+                    crc.update_with_zeros(zeros);
                 });
             },
         );
@@ -1,25 +1,7 @@
-use std::{
-    env,
-    error::Error,
-    fs::{self, File},
-    io::{BufWriter, Write},
-};
+use std::{env, error::Error, fs};
 
 use regex::{Captures, Regex};
 
-// ======================================================================
-// CONST
-
-// CRC-32C (Castagnoli)
-const POLYNOMIAL_32: u32 = 0x1EDC6F41;
-const REVERSED_POLYNOMIAL_32: u32 = 0x82F63B78;
-
-// CRC-64/XZ
-const POLYNOMIAL_64: u64 = 0x42F0E1EBA9EA3693;
-const REVERSED_POLYNOMIAL_64: u64 = 0xC96C5795D7870F42;
-
-const USIZE_BITS: usize = usize::BITS as usize;
-
 // ======================================================================
 // MAIN
 
@@ -33,166 +15,6 @@ fn main() -> Result<(), Box<dyn Error>> {
     let readme = rustdocify_readme(&readme)?;
     fs::write(out_dir.join("README-rustdocified.md"), readme)?;
 
-    // TABLES
-
-    let tables_path = out_dir.join("tables.rs");
-    let mut w = BufWriter::new(File::create(&tables_path)?);
-    write_crc32_table(&mut w)?;
-    write_crc64_table(&mut w)?;
-    write_pow256_32_table(&mut w)?;
-    write_pow256_64_table(&mut w)?;
-    w.flush()?;
-
-    // See: https://github.com/rust-lang/rust/issues/75075
-    println!(
-        "cargo:rustc-env=GENERATED_TABLES_RS={}",
-        tables_path.display()
-    );
-
-    Ok(())
-}
-
-// ======================================================================
-// CRC TABLES
-
-fn write_crc32_table<W: Write>(w: &mut W) -> Result<(), Box<dyn Error>> {
-    let mut table = [[0u32; 256]; 8];
-
-    for byte in 0..256 {
-        let mut crc = byte as u32;
-        for _ in 0..8 {
-            crc = (crc >> 1) ^ (0u32.wrapping_sub(crc & 1) & REVERSED_POLYNOMIAL_32);
-        }
-        table[0][byte] = crc;
-    }
-
-    for byte in 0..256 {
-        table[1][byte] = (table[0][byte] >> 8) ^ table[0][table[0][byte] as usize & 0xFF];
-        table[2][byte] = (table[1][byte] >> 8) ^ table[0][table[1][byte] as usize & 0xFF];
-        table[3][byte] = (table[2][byte] >> 8) ^ table[0][table[2][byte] as usize & 0xFF];
-        table[4][byte] = (table[3][byte] >> 8) ^ table[0][table[3][byte] as usize & 0xFF];
-        table[5][byte] = (table[4][byte] >> 8) ^ table[0][table[4][byte] as usize & 0xFF];
-        table[6][byte] = (table[5][byte] >> 8) ^ table[0][table[5][byte] as usize & 0xFF];
-        table[7][byte] = (table[6][byte] >> 8) ^ table[0][table[6][byte] as usize & 0xFF];
-    }
-
-    writeln!(w, "static CRC32: [[u32; 256]; 8] = [")?;
-    for inner in table {
-        writeln!(w, "[")?;
-        for (byte, x) in inner.iter().enumerate() {
-            write!(w, "0x{:08X}, ", x)?;
-            if byte % 8 == 7 {
-                writeln!(w)?;
-            }
-        }
-        writeln!(w, "],")?;
-    }
-    writeln!(w, "];")?;
-
-    Ok(())
-}
-
-fn write_crc64_table<W: Write>(w: &mut W) -> Result<(), Box<dyn Error>> {
-    let mut table = [[0u64; 256]; 8];
-
-    for byte in 0..256 {
-        let mut crc = byte as u64;
-        for _ in 0..8 {
-            crc = (crc >> 1) ^ (0u64.wrapping_sub(crc & 1) & REVERSED_POLYNOMIAL_64);
-        }
-        table[0][byte] = crc;
-    }
-
-    for byte in 0..256 {
-        table[1][byte] = (table[0][byte] >> 8) ^ table[0][table[0][byte] as usize & 0xFF];
-        table[2][byte] = (table[1][byte] >> 8) ^ table[0][table[1][byte] as usize & 0xFF];
-        table[3][byte] = (table[2][byte] >> 8) ^ table[0][table[2][byte] as usize & 0xFF];
-        table[4][byte] = (table[3][byte] >> 8) ^ table[0][table[3][byte] as usize & 0xFF];
-        table[5][byte] = (table[4][byte] >> 8) ^ table[0][table[4][byte] as usize & 0xFF];
-        table[6][byte] = (table[5][byte] >> 8) ^ table[0][table[5][byte] as usize & 0xFF];
-        table[7][byte] = (table[6][byte] >> 8) ^ table[0][table[6][byte] as usize & 0xFF];
-    }
-
-    writeln!(w, "static CRC64: [[u64; 256]; 8] = [")?;
-    for inner in table {
-        writeln!(w, "[")?;
-        for (byte, x) in inner.iter().enumerate() {
-            write!(w, "0x{:016X}, ", x)?;
-            if byte % 4 == 3 {
-                writeln!(w)?;
-            }
-        }
-        writeln!(w, "],")?;
-    }
-    writeln!(w, "];")?;
-
-    Ok(())
-}
-
-// ======================================================================
-// ZEROS TABLES
-
-// Computes `a * b` in Galois field.
-// - copied from zeros.rs
-fn mul32(a: u32, mut b: u32) -> u32 {
-    let mut product = 0;
-    for _ in 0..32 {
-        product = (product << 1) ^ (0u32.wrapping_sub(product >> 31) & POLYNOMIAL_32);
-        product ^= (0u32.wrapping_sub(b >> 31)) & a;
-        b <<= 1;
-    }
-    product
-}
-
-// Computes `a * b` in Galois field.
-// - copied from zeros.rs
-fn mul64(a: u64, mut b: u64) -> u64 {
-    let mut product = 0;
-    for _ in 0..64 {
-        product = (product << 1) ^ (0u64.wrapping_sub(product >> 63) & POLYNOMIAL_64);
-        product ^= (0u64.wrapping_sub(b >> 63)) & a;
-        b <<= 1;
-    }
-    product
-}
-
-fn write_pow256_32_table<W: Write>(w: &mut W) -> Result<(), Box<dyn Error>> {
-    let mut table = [0u32; USIZE_BITS];
-
-    table[0] = 256;
-    for n in 1..USIZE_BITS {
-        table[n] = mul32(table[n - 1], table[n - 1]);
-    }
-
-    writeln!(w, "pub(crate) static POW256_32: [u32; {}] = [", USIZE_BITS)?;
-    for (n, x) in table.iter().enumerate() {
-        write!(w, "0x{:08X}, ", x)?;
-        if n % 8 == 7 {
-            writeln!(w)?;
-        }
-    }
-    writeln!(w, "\n];")?;
-
-    Ok(())
-}
-
-fn write_pow256_64_table<W: Write>(w: &mut W) -> Result<(), Box<dyn Error>> {
-    let mut table = [0u64; USIZE_BITS];
-
-    table[0] = 256;
-    for n in 1..USIZE_BITS {
-        table[n] = mul64(table[n - 1], table[n - 1]);
-    }
-
-    writeln!(w, "pub(crate) static POW256_64: [u64; {}] = [", USIZE_BITS)?;
-    for (n, x) in table.iter().enumerate() {
-        write!(w, "0x{:016X}, ", x)?;
-        if n % 4 == 3 {
-            writeln!(w)?;
-        }
-    }
-    writeln!(w, "\n];")?;
-
     Ok(())
 }
 
@@ -207,6 +29,10 @@ fn write_pow256_64_table<W: Write>(w: &mut W) -> Result<(), Box<dyn Error>> {
 //       -->  crate::STRUCT
 //     - https://docs.rs/PACKAGE/VERSION/CRATE/struct.STRUCT.html#method.METHOD
 //       -->  crate::STRUCT::METHOD
+//     - Without `std` feature links are kept as is,
+//       since many linked items need `alloc` or `std`.
+// - Without `alloc` feature mark examples as `ignore`,
+//   since some of them use items which need it.
 // - Remove top-level header.
 // - Change other headers to be one level higher.
 fn rustdocify_readme(readme: &str) -> Result<String, Box<dyn Error>> {
@@ -217,6 +43,8 @@ fn rustdocify_readme(readme: &str) -> Result<String, Box<dyn Error>> {
 
     let version = env::var("CARGO_PKG_VERSION")?;
 
+    let keep_links = env::var_os("CARGO_FEATURE_STD").is_none();
+
     // CONVERT INTERNAL LINKS
 
     let re_links = Regex::new(&format!(
@@ -240,6 +68,10 @@ fn rustdocify_readme(readme: &str) -> Result<String, Box<dyn Error>> {
                 );
             }
 
+            if keep_links {
+                return cap[0].to_owned();
+            }
+
             let struct_name = &cap[3];
 
             if let Some(method_name) = cap.get(4) {
@@ -250,6 +82,14 @@ fn rustdocify_readme(readme: &str) -> Result<String, Box<dyn Error>> {
         })
         .into_owned();
 
+    // EXAMPLES
+
+    let readme = if env::var_os("CARGO_FEATURE_ALLOC").is_none() {
+        readme.replace("```rust\n", "```rust,ignore\n")
+    } else {
+        readme
+    };
+
     // HEADERS
 
     let re_headers = Regex::new(r"(?m)^(#+)(.+)$")?;
@@ -0,0 +1,554 @@
+//! `rdcrc` - print or check `CRC-32C` and `CRC-64/XZ` checksums of files.
+//!
+//! Requires feature `cli`. Run `rdcrc --help` for usage.
+
+#![forbid(unsafe_code)]
+
+use std::{
+    env,
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use rolling_dual_crc::{CheckStatus, DualCrc, DualCrcReader, ManifestEntry};
+
+// ======================================================================
+// CONST
+
+const USAGE: &str = "\
+Usage: rdcrc [OPTION]... [FILE]...
+Print or check CRC-32C and CRC-64/XZ checksums of files.
+
+With no FILE, or when FILE is -, read standard input.
+
+  -32, --crc32      print only CRC-32C
+  -64, --crc64      print only CRC-64/XZ
+  -x,  --hex        print checksums as hexadecimal (default)
+  -d,  --decimal    print checksums as decimal
+  -b,  --base64     print big-endian checksums as base64
+       --tag        print BSD-style checksums (hexadecimal only)
+  -r,  --recursive  process directories recursively
+  -c,  --check      read checksums from the FILEs and check them
+  -h,  --help       print this help and exit
+
+In check mode FILEs contain lines printed by rdcrc in hexadecimal,
+with or without --tag. Exit status is 0 on success, and 1 if any
+checksum did not match, any file could not be read, or usage is invalid.
+";
+
+// ======================================================================
+// Options
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Width {
+    Both,
+    Crc32,
+    Crc64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Hex,
+    Decimal,
+    Base64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Options {
+    width: Width,
+    format: Format,
+    tag: bool,
+    recursive: bool,
+    check: bool,
+    paths: Vec<PathBuf>,
+}
+
+impl Options {
+    /// Parses command line arguments, excluding program name.
+    ///
+    /// Returns `Ok(None)` if help was requested.
+    fn parse<I: IntoIterator<Item = OsString>>(args: I) -> Result<Option<Self>, String> {
+        let mut options = Options {
+            width: Width::Both,
+            format: Format::Hex,
+            tag: false,
+            recursive: false,
+            check: false,
+            paths: Vec::new(),
+        };
+
+        let mut only_paths = false;
+        for arg in args {
+            if only_paths || arg == "-" || !arg.to_string_lossy().starts_with('-') {
+                options.paths.push(PathBuf::from(arg));
+                continue;
+            }
+
+            match arg.to_string_lossy().as_ref() {
+                "--" => only_paths = true,
+                "-32" | "--crc32" => options.width = Width::Crc32,
+                "-64" | "--crc64" => options.width = Width::Crc64,
+                "-x" | "--hex" => options.format = Format::Hex,
+                "-d" | "--decimal" => options.format = Format::Decimal,
+                "-b" | "--base64" => options.format = Format::Base64,
+                "--tag" => options.tag = true,
+                "-r" | "--recursive" => options.recursive = true,
+                "-c" | "--check" => options.check = true,
+                "-h" | "--help" => return Ok(None),
+                other => return Err(format!("unknown option: {}", other)),
+            }
+        }
+
+        if options.tag && options.format != Format::Hex {
+            return Err("--tag requires hexadecimal output".to_owned());
+        }
+
+        if options.paths.is_empty() {
+            options.paths.push(PathBuf::from("-"));
+        }
+
+        Ok(Some(options))
+    }
+}
+
+// ======================================================================
+// MAIN
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args_os().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("rdcrc: {}\n\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    let mut ok = true;
+    for path in &options.paths {
+        if options.check {
+            ok &= check_path(path, &mut out);
+        } else {
+            ok &= process_path(path, &options, &mut out);
+        }
+    }
+
+    if out.flush().is_err() || !ok {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// ======================================================================
+// FUNCTIONS
+
+/// Prints checksums of given file, stdin or directory.
+///
+/// Errors are printed to stderr. Returns `false` if there were any errors.
+fn process_path<W: Write>(path: &Path, options: &Options, out: &mut W) -> bool {
+    let result = if path == Path::new("-") {
+        checksum_reader(io::stdin().lock())
+    } else {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => {
+                if options.recursive {
+                    return process_dir(path, options, out);
+                }
+                eprintln!("rdcrc: {}: Is a directory", path.display());
+                return false;
+            }
+            Ok(_) => File::open(path).and_then(checksum_reader),
+            Err(error) => Err(error),
+        }
+    };
+
+    match result {
+        Ok(crc) => {
+            let name = path.to_string_lossy();
+            let line = if options.tag {
+                format_tagged_line(crc, options.width, &name)
+            } else {
+                format_line(crc, options.width, options.format, &name)
+            };
+            writeln!(out, "{}", line).is_ok()
+        }
+        Err(error) => {
+            eprintln!("rdcrc: {}: {}", path.display(), error);
+            false
+        }
+    }
+}
+
+/// Prints checksums of all files in given directory and its subdirectories,
+/// in sorted order. Symbolic links to directories are not followed.
+fn process_dir<W: Write>(path: &Path, options: &Options, out: &mut W) -> bool {
+    let mut entries = match fs::read_dir(path).and_then(|dir| dir.collect::<io::Result<Vec<_>>>()) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("rdcrc: {}: {}", path.display(), error);
+            return false;
+        }
+    };
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut ok = true;
+    for entry in entries {
+        let entry_path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_symlink() && entry_path.is_dir() => {}
+            Ok(_) => ok &= process_path(&entry_path, options, out),
+            Err(error) => {
+                eprintln!("rdcrc: {}: {}", entry_path.display(), error);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+/// Checks files listed in given manifest file or stdin.
+///
+/// Results are printed to `out` and warnings to stderr.
+/// Returns `false` if any file failed or there were any errors.
+fn check_path<W: Write>(path: &Path, out: &mut W) -> bool {
+    let name = path.display();
+    let result = if path == Path::new("-") {
+        check_manifest(io::stdin().lock(), out)
+    } else {
+        File::open(path).and_then(|file| check_manifest(BufReader::new(file), out))
+    };
+
+    let counts = match result {
+        Ok(counts) => counts,
+        Err(error) => {
+            eprintln!("rdcrc: {}: {}", name, error);
+            return false;
+        }
+    };
+
+    if counts.improper > 0 {
+        eprintln!(
+            "rdcrc: WARNING: {} {} improperly formatted",
+            counts.improper,
+            plural(counts.improper, "line is", "lines are")
+        );
+    }
+    if counts.missing > 0 {
+        eprintln!(
+            "rdcrc: WARNING: {} listed {} could not be read",
+            counts.missing,
+            plural(counts.missing, "file", "files")
+        );
+    }
+    if counts.failed > 0 {
+        eprintln!(
+            "rdcrc: WARNING: {} computed {} did NOT match",
+            counts.failed,
+            plural(counts.failed, "checksum", "checksums")
+        );
+    }
+
+    if counts.checked == 0 {
+        eprintln!(
+            "rdcrc: {}: no properly formatted checksum lines found",
+            name
+        );
+        return false;
+    }
+
+    counts.failed == 0 && counts.missing == 0
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct CheckCounts {
+    checked: usize,
+    improper: usize,
+    failed: usize,
+    missing: usize,
+}
+
+/// Checks files listed in given manifest, printing result of each file to `out`.
+///
+/// Improperly formatted lines are counted but otherwise ignored.
+fn check_manifest<R: BufRead, W: Write>(reader: R, out: &mut W) -> io::Result<CheckCounts> {
+    let mut counts = CheckCounts::default();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if line.is_empty() {
+            continue;
+        }
+
+        let entry = match ManifestEntry::parse(line) {
+            Some(entry) => entry,
+            None => {
+                counts.improper += 1;
+                continue;
+            }
+        };
+
+        counts.checked += 1;
+        let result = match entry.verify() {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Failed => {
+                counts.failed += 1;
+                "FAILED"
+            }
+            CheckStatus::Missing => {
+                counts.missing += 1;
+                "FAILED open or read"
+            }
+        };
+        writeln!(out, "{}: {}", entry.path.display(), result)?;
+    }
+
+    Ok(counts)
+}
+
+fn plural<'a>(count: usize, singular: &'a str, plural: &'a str) -> &'a str {
+    if count == 1 {
+        singular
+    } else {
+        plural
+    }
+}
+
+/// Computes checksums of all data from given reader.
+fn checksum_reader<R: Read>(reader: R) -> io::Result<DualCrc> {
+    let mut reader = DualCrcReader::new(reader);
+    io::copy(&mut reader, &mut io::sink())?;
+    Ok(reader.crc())
+}
+
+/// Formats checksums and name as one output line, without newline.
+fn format_line(crc: DualCrc, width: Width, format: Format, name: &str) -> String {
+    let crc32 = match format {
+        Format::Hex => format!("{:08x}", crc.get32()),
+        Format::Decimal => format!("{}", crc.get32()),
+        Format::Base64 => base64(&crc.get32().to_be_bytes()),
+    };
+
+    let crc64 = match format {
+        Format::Hex => format!("{:016x}", crc.get64()),
+        Format::Decimal => format!("{}", crc.get64()),
+        Format::Base64 => base64(&crc.get64().to_be_bytes()),
+    };
+
+    match width {
+        Width::Both => format!("{} {}  {}", crc32, crc64, name),
+        Width::Crc32 => format!("{}  {}", crc32, name),
+        Width::Crc64 => format!("{}  {}", crc64, name),
+    }
+}
+
+/// Formats checksums and name as BSD-style lines, without final newline.
+fn format_tagged_line(crc: DualCrc, width: Width, name: &str) -> String {
+    let (crc32, crc64) = crc.get();
+    let entry = |crc32, crc64| ManifestEntry {
+        path: name.into(),
+        crc32,
+        crc64,
+    };
+
+    match width {
+        Width::Both => format!("{}\n{}", entry(Some(crc32), None), entry(None, Some(crc64))),
+        Width::Crc32 => entry(Some(crc32), None).to_string(),
+        Width::Crc64 => entry(None, Some(crc64)).to_string(),
+    }
+}
+
+/// Encodes given bytes as standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(OsString::from))
+    }
+
+    // ============================================================
+    // Options
+
+    #[test]
+    fn parse_defaults() {
+        assert_eq!(
+            parse(&[]),
+            Ok(Some(Options {
+                width: Width::Both,
+                format: Format::Hex,
+                tag: false,
+                recursive: false,
+                check: false,
+                paths: vec![PathBuf::from("-")],
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_options_and_paths() {
+        assert_eq!(
+            parse(&["-64", "a", "--base64", "-r", "-", "--", "-d"]),
+            Ok(Some(Options {
+                width: Width::Crc64,
+                format: Format::Base64,
+                tag: false,
+                recursive: true,
+                check: false,
+                paths: vec!["a".into(), "-".into(), "-d".into()],
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_check_and_tag() {
+        let options = parse(&["-c", "--tag", "a"]).unwrap().unwrap();
+        assert!(options.check);
+        assert!(options.tag);
+        assert!(parse(&["--tag", "-d"]).is_err());
+    }
+
+    #[test]
+    fn parse_help_and_errors() {
+        assert_eq!(parse(&["a", "--help"]), Ok(None));
+        assert!(parse(&["--unknown"]).is_err());
+    }
+
+    // ============================================================
+    // checksum_reader / format_line
+
+    #[test]
+    fn checksum_reader_large() {
+        let data = b"123456789".repeat(20_000);
+        let crc = checksum_reader(&data[..]).unwrap();
+        assert_eq!(crc.get(), DualCrc::checksum(&data));
+    }
+
+    #[test]
+    fn format_line_all() {
+        let crc = checksum_reader(&b"123456789"[..]).unwrap();
+
+        // "check" values from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(
+            format_line(crc, Width::Both, Format::Hex, "f"),
+            "e3069283 995dc9bbdf1939fa  f"
+        );
+        assert_eq!(
+            format_line(crc, Width::Crc32, Format::Decimal, "f"),
+            "3808858755  f"
+        );
+        assert_eq!(
+            format_line(crc, Width::Crc64, Format::Base64, "f"),
+            "mV3Ju98ZOfo=  f"
+        );
+    }
+
+    #[test]
+    fn format_tagged_line_all() {
+        let crc = checksum_reader(&b"123456789"[..]).unwrap();
+        assert_eq!(
+            format_tagged_line(crc, Width::Both, "f"),
+            "CRC32C (f) = e3069283\nCRC64XZ (f) = 995dc9bbdf1939fa"
+        );
+        assert_eq!(
+            format_tagged_line(crc, Width::Crc64, "f"),
+            "CRC64XZ (f) = 995dc9bbdf1939fa"
+        );
+    }
+
+    // ============================================================
+    // check_manifest
+
+    #[test]
+    fn check_manifest_results() {
+        let dir = env::temp_dir().join(format!("rdcrc-check-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("check.txt");
+        fs::write(&path, "123456789").unwrap();
+        let path = path.display();
+        let missing = dir.join("missing.txt");
+        let missing = missing.display();
+
+        let manifest = format!(
+            "e3069283 995dc9bbdf1939fa  {path}\n\
+             CRC64XZ ({path}) = 995dc9bbdf1939fa\n\
+             00000000  {path}\n\
+             improper\n\
+             e3069283  {missing}\n",
+            path = path,
+            missing = missing,
+        );
+
+        let mut out = Vec::new();
+        let counts = check_manifest(manifest.as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            counts,
+            CheckCounts {
+                checked: 4,
+                improper: 1,
+                failed: 1,
+                missing: 1,
+            }
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "{path}: OK\n{path}: OK\n{path}: FAILED\n{missing}: FAILED open or read\n",
+                path = path,
+                missing = missing,
+            )
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // ============================================================
+    // base64
+
+    #[test]
+    fn base64_padding() {
+        // test vectors from RFC 4648
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
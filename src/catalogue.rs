@@ -0,0 +1,83 @@
+//! Common CRC algorithms for [`Crc`](crate::Crc).
+//!
+//! Names and parameters are from [Catalogue of parametrised CRC algorithms],
+//! except that `CRC-64/XZ` is [`Crc64XzAlgo`] since [`Crc64Xz`](crate::Crc64Xz)
+//! is the iterative hasher of the crate root.
+//! So both modules can be glob-imported together:
+//!
+//! ```rust
+//! use rolling_dual_crc::{catalogue::*, *};
+//!
+//! assert_eq!(Crc::<Crc64XzAlgo>::checksum("123456789"), Crc64Xz::checksum("123456789"));
+//! ```
+//!
+//! [Catalogue of parametrised CRC algorithms]: https://reveng.sourceforge.io/crc-catalogue/all.htm
+
+use crate::{CrcAlgorithm, CrcParams};
+
+// ======================================================================
+// ALGORITHMS - PUBLIC
+
+/// `CRC-32/ISCSI`, also known as `CRC-32C (Castagnoli)`.
+///
+/// This is the 32-bit checksum of [`DualCrc`](crate::DualCrc).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Crc32Iscsi;
+
+impl CrcAlgorithm for Crc32Iscsi {
+    const PARAMS: CrcParams = CrcParams {
+        width: 32,
+        poly: 0x1EDC6F41,
+        init: 0xFFFFFFFF,
+        refin: true,
+        refout: true,
+        xorout: 0xFFFFFFFF,
+    };
+}
+
+/// `CRC-32/ISO-HDLC`, the checksum of zlib, gzip, zip and PNG.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Crc32IsoHdlc;
+
+impl CrcAlgorithm for Crc32IsoHdlc {
+    const PARAMS: CrcParams = CrcParams {
+        width: 32,
+        poly: 0x04C11DB7,
+        init: 0xFFFFFFFF,
+        refin: true,
+        refout: true,
+        xorout: 0xFFFFFFFF,
+    };
+}
+
+/// `CRC-64/NVME`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Crc64Nvme;
+
+impl CrcAlgorithm for Crc64Nvme {
+    const PARAMS: CrcParams = CrcParams {
+        width: 64,
+        poly: 0xAD93D23594C93659,
+        init: 0xFFFFFFFFFFFFFFFF,
+        refin: true,
+        refout: true,
+        xorout: 0xFFFFFFFFFFFFFFFF,
+    };
+}
+
+/// `CRC-64/XZ`.
+///
+/// This is the 64-bit checksum of [`DualCrc`](crate::DualCrc).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Crc64XzAlgo;
+
+impl CrcAlgorithm for Crc64XzAlgo {
+    const PARAMS: CrcParams = CrcParams {
+        width: 64,
+        poly: 0x42F0E1EBA9EA3693,
+        init: 0xFFFFFFFFFFFFFFFF,
+        refin: true,
+        refout: true,
+        xorout: 0xFFFFFFFFFFFFFFFF,
+    };
+}
@@ -0,0 +1,459 @@
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+#[cfg(feature = "std")]
+use alloc::{vec, vec::Vec};
+
+use crate::{DualCrc, RollingDualCrc};
+
+// ======================================================================
+// CONST - PUBLIC
+
+/// Size of the rolling window used by [`Chunker`] and [`ChunkReader`]
+/// to find chunk boundaries.
+///
+#[cfg_attr(feature = "std", doc = "[`ChunkReader`]: crate::ChunkReader")]
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "[`ChunkReader`]: https://docs.rs/rolling-dual-crc/0.1.0/rolling_dual_crc/struct.ChunkReader.html"
+)]
+pub const CHUNKER_WINDOW_SIZE: usize = 48;
+
+// ======================================================================
+// CONST - PRIVATE
+
+#[cfg(feature = "std")]
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+// ======================================================================
+// Chunker - PUBLIC
+
+/// Splits data into content-defined chunks using [`RollingDualCrc`].
+///
+/// Chunk boundaries are found by rolling a window of [`CHUNKER_WINDOW_SIZE`] bytes
+/// through the data and cutting after a byte where masked bits of
+/// [`get64`] are all zero. Since boundaries depend only on local content,
+/// inserting or removing data changes only the nearby chunks.
+///
+/// Chunk sizes are controlled FastCDC-style:
+///
+/// - No boundary is placed before `min_size` bytes.
+/// - Until `avg_size` bytes a stricter mask (one more bit) is used and after
+///   that a looser mask (one less bit), which concentrates sizes near `avg_size`.
+/// - Chunk is always cut at `max_size` bytes.
+///
+/// Iterator yields `(offset, len, crc)` for each chunk,
+/// where `crc` has the checksums of the chunk.
+/// All chunks except the last one have size `min_size ..= max_size`.
+///
+/// See [`ChunkReader`] for chunking data from [`Read`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{Chunker, DualCrc};
+///
+/// let data: Vec<u8> = (0..100_000u32).map(|x| (x.wrapping_mul(2654435761) >> 13) as u8).collect();
+///
+/// let mut expected_offset = 0;
+/// for (offset, len, crc) in Chunker::new(&data, 1024, 4096, 16384) {
+///     assert_eq!(offset, expected_offset);
+///     assert_eq!(crc.get(), DualCrc::checksum(&data[offset..offset + len]));
+///     expected_offset += len;
+/// }
+/// assert_eq!(expected_offset, data.len());
+/// ```
+///
+/// [`get64`]: RollingDualCrc::get64
+/// [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+#[cfg_attr(feature = "std", doc = "[`ChunkReader`]: crate::ChunkReader")]
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "[`ChunkReader`]: https://docs.rs/rolling-dual-crc/0.1.0/rolling_dual_crc/struct.ChunkReader.html"
+)]
+#[derive(Clone, Debug)]
+pub struct Chunker<'a> {
+    data: &'a [u8],
+    pos: usize,
+    boundary: Boundary,
+}
+
+impl<'a> Chunker<'a> {
+    /// Creates a new [`Chunker`] which splits given data into chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < min_size <= avg_size <= max_size`
+    /// and `avg_size` is a power of two greater than one.
+    ///
+    /// See [`Chunker`] for an example.
+    pub fn new(data: &'a [u8], min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        Self {
+            data,
+            pos: 0,
+            boundary: Boundary::new(min_size, avg_size, max_size),
+        }
+    }
+}
+
+// ======================================================================
+// Chunker - IMPL Iterator
+
+impl<'a> Iterator for Chunker<'a> {
+    type Item = (usize, usize, DualCrc);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.data.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        let (len, _) = self.boundary.find(&self.data[start..]);
+        self.pos += len;
+
+        let mut crc = DualCrc::new();
+        crc.update(&self.data[start..self.pos]);
+        Some((start, len, crc))
+    }
+}
+
+// ======================================================================
+// ChunkReader - PUBLIC
+
+/// Splits data from [`Read`] into content-defined chunks using [`RollingDualCrc`].
+///
+/// This finds same chunks as [`Chunker`] would find for all the data,
+/// but reads data in blocks, keeping only the current chunk in memory.
+///
+/// Iterator yields `Ok((offset, len, crc))` for each chunk
+/// and [`chunk`] returns the data of the latest chunk.
+/// On I/O error `Err` is returned and reading can be retried with next call.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{ChunkReader, DualCrc};
+///
+/// let data: Vec<u8> = (0..100_000u32).map(|x| (x.wrapping_mul(2654435761) >> 13) as u8).collect();
+///
+/// let mut reader = ChunkReader::new(&data[..], 1024, 4096, 16384);
+/// while let Some(result) = reader.next() {
+///     let (offset, len, crc) = result.unwrap();
+///     let offset = offset as usize;
+///     assert_eq!(reader.chunk(), &data[offset..offset + len]);
+///     assert_eq!(crc.get(), DualCrc::checksum(reader.chunk()));
+/// }
+/// ```
+///
+/// [`chunk`]: ChunkReader::chunk
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct ChunkReader<R> {
+    reader: R,
+    offset: u64,
+    boundary: Boundary,
+
+    buffer: Vec<u8>,
+    buffer_pos: usize,
+    buffer_len: usize,
+
+    chunk: Vec<u8>,
+    chunk_done: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R> ChunkReader<R> {
+    /// Returns data of the chunk returned by latest call to `next`.
+    ///
+    /// Returns empty slice before first chunk and after end of data.
+    pub fn chunk(&self) -> &[u8] {
+        if self.chunk_done {
+            &self.chunk
+        } else {
+            &[]
+        }
+    }
+
+    /// Unwraps this [`ChunkReader`], returning the inner reader.
+    ///
+    /// Any data which has been read but not yet returned as chunk is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Creates a new [`ChunkReader`] which splits data from given reader into chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < min_size <= avg_size <= max_size`
+    /// and `avg_size` is a power of two greater than one.
+    ///
+    /// See [`ChunkReader`] for an example.
+    pub fn new(reader: R, min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        Self {
+            reader,
+            offset: 0,
+            boundary: Boundary::new(min_size, avg_size, max_size),
+
+            buffer: vec![0u8; READ_BUFFER_SIZE],
+            buffer_pos: 0,
+            buffer_len: 0,
+
+            chunk: Vec::new(),
+            chunk_done: false,
+        }
+    }
+}
+
+// ======================================================================
+// ChunkReader - IMPL Iterator
+
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for ChunkReader<R> {
+    type Item = io::Result<(u64, usize, DualCrc)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.chunk_done {
+            self.chunk.clear();
+            self.chunk_done = false;
+        }
+
+        loop {
+            if self.buffer_pos == self.buffer_len {
+                match self.reader.read(&mut self.buffer) {
+                    Ok(0) => break,
+                    Ok(len) => {
+                        self.buffer_pos = 0;
+                        self.buffer_len = len;
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(error) => return Some(Err(error)),
+                }
+            }
+
+            let data = &self.buffer[self.buffer_pos..self.buffer_len];
+            let (len, is_boundary) = self.boundary.find(data);
+            self.chunk.extend_from_slice(&data[..len]);
+            self.buffer_pos += len;
+
+            if is_boundary {
+                break;
+            }
+        }
+
+        if self.chunk.is_empty() {
+            return None;
+        }
+
+        self.chunk_done = true;
+
+        let offset = self.offset;
+        self.offset += self.chunk.len() as u64;
+
+        let mut crc = DualCrc::new();
+        crc.update(&self.chunk);
+        Some(Ok((offset, self.chunk.len(), crc)))
+    }
+}
+
+// ======================================================================
+// Boundary - PRIVATE
+
+/// Rolling state for finding chunk boundaries.
+#[derive(Clone, Debug)]
+struct Boundary {
+    crc: RollingDualCrc,
+    chunk_len: usize,
+
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+
+    strict_mask: u64,
+    loose_mask: u64,
+}
+
+impl Boundary {
+    fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        if !(0 < min_size && min_size <= avg_size && avg_size <= max_size) {
+            panic!("chunk sizes must be 0 < min_size <= avg_size <= max_size");
+        }
+
+        if avg_size < 2 || !avg_size.is_power_of_two() {
+            panic!("avg_size must be a power of two greater than one");
+        }
+
+        let bits = avg_size.trailing_zeros();
+
+        Self {
+            crc: RollingDualCrc::new([0u8; CHUNKER_WINDOW_SIZE]),
+            chunk_len: 0,
+
+            min_size,
+            avg_size,
+            max_size,
+
+            strict_mask: mask(bits + 1),
+            loose_mask: mask(bits - 1),
+        }
+    }
+
+    /// Rolls given data until a chunk boundary is found.
+    ///
+    /// Returns number of bytes rolled and whether that ended the chunk.
+    #[inline]
+    fn find(&mut self, data: &[u8]) -> (usize, bool) {
+        for (n, byte) in data.iter().enumerate() {
+            self.crc.roll(*byte);
+            self.chunk_len += 1;
+
+            if self.chunk_len < self.min_size {
+                continue;
+            }
+
+            let mask = if self.chunk_len < self.avg_size {
+                self.strict_mask
+            } else {
+                self.loose_mask
+            };
+
+            if self.chunk_len >= self.max_size || self.crc.get64() & mask == 0 {
+                self.chunk_len = 0;
+                return (n + 1, true);
+            }
+        }
+
+        (data.len(), false)
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+/// Returns mask with lowest `bits` bits set.
+fn mask(bits: u32) -> u64 {
+    if bits == 0 {
+        0
+    } else {
+        u64::MAX >> (64 - bits.min(64))
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::test_util::random_data;
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn avg_size_not_power_of_two() {
+        Chunker::new(&[], 100, 1000, 10000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn min_size_larger_than_avg_size() {
+        Chunker::new(&[], 2000, 1024, 10000);
+    }
+
+    // ============================================================
+    // Chunker
+
+    #[test]
+    fn chunker_empty() {
+        assert_eq!(Chunker::new(&[], 16, 64, 256).count(), 0);
+    }
+
+    #[test]
+    fn chunker_sizes_and_checksums() {
+        let data = random_data(1 << 20);
+
+        let mut expected_offset = 0;
+        let mut chunk_count = 0;
+        for (offset, len, crc) in Chunker::new(&data, 1024, 4096, 16384) {
+            assert_eq!(offset, expected_offset);
+            if offset + len < data.len() {
+                assert!((1024..=16384).contains(&len));
+            }
+            assert_eq!(crc.get(), DualCrc::checksum(&data[offset..offset + len]));
+            expected_offset += len;
+            chunk_count += 1;
+        }
+        assert_eq!(expected_offset, data.len());
+
+        // average size should be roughly `avg_size`
+        assert!((128..=512).contains(&chunk_count));
+    }
+
+    #[test]
+    fn chunker_max_size() {
+        let data = random_data(1000);
+        let lens: Vec<_> = Chunker::new(&data, 64, 64, 64)
+            .map(|(_, len, _)| len)
+            .collect();
+        assert_eq!(
+            lens,
+            [64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 40]
+        );
+    }
+
+    #[test]
+    fn chunker_resynchronizes() {
+        let data = random_data(1 << 18);
+        let mut modified = data.clone();
+        modified.insert(1000, b'x');
+
+        let chunks: Vec<_> = Chunker::new(&data, 256, 1024, 4096)
+            .map(|(offset, len, crc)| (offset + 1, len, crc.get()))
+            .collect();
+        let modified_chunks: Vec<_> = Chunker::new(&modified, 256, 1024, 4096)
+            .map(|(offset, len, crc)| (offset, len, crc.get()))
+            .collect();
+
+        // all but first few chunks are same
+        let common = chunks
+            .iter()
+            .rev()
+            .zip(modified_chunks.iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        assert!(common + 3 >= chunks.len());
+    }
+
+    // ============================================================
+    // ChunkReader
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn chunk_reader_same_as_chunker() {
+        // larger than `READ_BUFFER_SIZE` to test buffer refilling
+        let data = random_data(300_000);
+
+        let mut reader = ChunkReader::new(&data[..], 1024, 4096, 16384);
+        assert_eq!(reader.chunk(), &[]);
+
+        let mut chunker = Chunker::new(&data, 1024, 4096, 16384);
+        while let Some(result) = reader.next() {
+            let (offset, len, crc) = result.unwrap();
+            let (expected_offset, expected_len, expected_crc) = chunker.next().unwrap();
+            assert_eq!(offset, expected_offset as u64);
+            assert_eq!(len, expected_len);
+            assert_eq!(crc.get(), expected_crc.get());
+            assert_eq!(
+                reader.chunk(),
+                &data[offset as usize..offset as usize + len]
+            );
+        }
+        assert!(chunker.next().is_none());
+        assert_eq!(reader.chunk(), &[]);
+    }
+}
@@ -0,0 +1,556 @@
+use core::{marker::PhantomData, ops::BitXor};
+
+// ======================================================================
+// CONST - CRATE
+
+pub(crate) const USIZE_BITS: usize = usize::BITS as usize;
+
+// ======================================================================
+// CrcParams - PUBLIC
+
+/// Parameters of a CRC algorithm in the Rocksoft model,
+/// as used by [Catalogue of parametrised CRC algorithms].
+///
+/// `poly`, `init` and `xorout` are given unreflected, as in the catalogue,
+/// and must fit in `width` bits.
+///
+/// See [`CrcAlgorithm`] for an example.
+///
+/// [Catalogue of parametrised CRC algorithms]: https://reveng.sourceforge.io/crc-catalogue/all.htm
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CrcParams {
+    /// Width of the CRC in bits, `1 ..= 64`.
+    pub width: u32,
+    /// Generator polynomial without the implicit highest bit.
+    pub poly: u64,
+    /// Initial value of the register.
+    pub init: u64,
+    /// Whether input bytes are reflected, i.e. processed least significant bit first.
+    pub refin: bool,
+    /// Whether the register is reflected before `xorout` is applied.
+    pub refout: bool,
+    /// Value XORed to the register to get the checksum.
+    pub xorout: u64,
+}
+
+// ======================================================================
+// CrcAlgorithm - PUBLIC
+
+/// CRC algorithm for [`Crc`], defined by its [`CrcParams`].
+///
+/// Common algorithms are provided in [`catalogue`](crate::catalogue).
+///
+/// # Examples
+///
+/// Define `CRC-32/BZIP2`:
+///
+/// ```rust
+/// use rolling_dual_crc::{Crc, CrcAlgorithm, CrcParams};
+///
+/// struct Crc32Bzip2;
+///
+/// impl CrcAlgorithm for Crc32Bzip2 {
+///     const PARAMS: CrcParams = CrcParams {
+///         width: 32,
+///         poly: 0x04C11DB7,
+///         init: 0xFFFFFFFF,
+///         refin: false,
+///         refout: false,
+///         xorout: 0xFFFFFFFF,
+///     };
+/// }
+///
+/// assert_eq!(Crc::<Crc32Bzip2>::checksum("123456789"), 0xFC891918);
+/// ```
+pub trait CrcAlgorithm {
+    /// Parameters of the algorithm.
+    ///
+    /// Invalid parameters, e.g. `width` of zero or `poly` wider than `width`,
+    /// cause a compile-time error when the algorithm is used with [`Crc`].
+    const PARAMS: CrcParams;
+}
+
+// ======================================================================
+// Crc - PUBLIC
+
+/// Generic CRC engine for computing checksums of any [`CrcAlgorithm`]
+/// in one go or iteratively.
+///
+/// Lookup tables are generated at compile time for each algorithm used.
+///
+/// Checksum is returned as `u64` regardless of `width` of the algorithm.
+///
+/// [`DualCrc`] computes `CRC-32C` and `CRC-64/XZ` using this same model,
+/// i.e. it gives same checksums as `Crc<Crc32Iscsi>` and `Crc<Crc64XzAlgo>`,
+/// but processes 8 bytes at a time and supports hardware acceleration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{catalogue::Crc32IsoHdlc, Crc};
+///
+/// assert_eq!(Crc::<Crc32IsoHdlc>::checksum("Hello, world!"), 0xEBE6C6E6);
+///
+/// let mut crc = Crc::<Crc32IsoHdlc>::new();
+/// crc.update("Hello");
+/// crc.update(", world!");
+/// assert_eq!(crc.get(), 0xEBE6C6E6);
+/// ```
+///
+/// [`DualCrc`]: crate::DualCrc
+#[derive(Clone, Copy, Debug)]
+pub struct Crc<A> {
+    pub(crate) register: u64,
+    algorithm: PhantomData<A>,
+}
+
+impl<A: CrcAlgorithm> Crc<A> {
+    /// Computes checksum of given data.
+    ///
+    /// See [`Crc`] for an example.
+    pub fn checksum<T: AsRef<[u8]>>(data: T) -> u64 {
+        let mut crc = Self::new();
+        crc.update(data);
+        crc.get()
+    }
+
+    /// Returns checksum of data processed so far.
+    ///
+    /// See [`Crc`] for an example.
+    pub fn get(&self) -> u64 {
+        register_to_checksum(self.register, &A::PARAMS)
+    }
+
+    /// Creates a new [`Crc`] for computing checksum iteratively.
+    ///
+    /// See [`Crc`] for an example.
+    pub fn new() -> Self {
+        Self {
+            register: Self::INIT,
+            algorithm: PhantomData,
+        }
+    }
+
+    /// Processes given data.
+    ///
+    /// See [`Crc`] for an example.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let table = Self::TABLE;
+        for byte in data.as_ref() {
+            self.register = update_register(self.register, table, A::PARAMS.refin, *byte);
+        }
+    }
+
+    /// Processes `byte_count` `0u8`:s.
+    ///
+    /// This is equivalent to `crc.update(&vec![0u8; byte_count])`,
+    /// but computed by multiplication in Galois field like [`Zeros`].
+    ///
+    /// Complexity: `Θ(one_bits n)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::{catalogue::Crc64Nvme, Crc};
+    ///
+    /// let mut crc = Crc::<Crc64Nvme>::new();
+    /// crc.update("abc");
+    /// crc.update_zeros(1000);
+    ///
+    /// let mut data = b"abc".to_vec();
+    /// data.resize(1003, 0);
+    /// assert_eq!(crc.get(), Crc::<Crc64Nvme>::checksum(&data));
+    /// ```
+    ///
+    /// [`Zeros`]: crate::Zeros
+    pub fn update_zeros(&mut self, byte_count: usize) {
+        let params = &A::PARAMS;
+        let factor = pow256(byte_count, Self::POW256, params);
+        let normal = mul(register_to_normal(self.register, params), factor, params);
+        self.register = normal_to_register(normal, params);
+    }
+}
+
+impl<A: CrcAlgorithm> Default for Crc<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ======================================================================
+// Crc - PRIVATE
+
+impl<A: CrcAlgorithm> Crc<A> {
+    const INIT: u64 = normal_to_register(A::PARAMS.init, &A::PARAMS);
+    const POW256: &'static [u64; USIZE_BITS] = &make_pow256_table(&A::PARAMS);
+}
+
+// ======================================================================
+// Crc - CRATE
+
+impl<A: CrcAlgorithm> Crc<A> {
+    pub(crate) const TABLE: &'static [u64; 256] = &make_table(&A::PARAMS);
+
+    /// Fills local lookup table for rolling window of given size.
+    ///
+    /// Table contains contribution of each possible byte
+    /// followed by `window_size` `0u8`:s, i.e. what is removed from
+    /// register when that byte leaves the window.
+    /// Contribution of `init` is included, since it also moves
+    /// forward by one byte when the window rolls.
+    ///
+    /// Only entries `0`, `1`, `2`, `4`, ..., `128` are computed with
+    /// multiplication in Galois field, see [`fill_table_from_basis`].
+    ///
+    /// Complexity: `Θ(log2 n)` time
+    #[cfg(feature = "alloc")]
+    pub(crate) fn fill_rolling_table(window_size: usize, table: &mut [u64; 256]) {
+        let params = &A::PARAMS;
+        let factor = pow256(window_size, Self::POW256, params);
+
+        for byte in [0, 1, 2, 4, 8, 16, 32, 64, 128] {
+            // `init` followed by `byte`, minus `init` of the window without `byte`
+            let register =
+                update_register(Self::INIT, Self::TABLE, params.refin, byte) ^ Self::INIT;
+            let normal = mul(register_to_normal(register, params), factor, params);
+            table[byte as usize] = normal_to_register(normal, params);
+        }
+
+        fill_table_from_basis(table);
+    }
+
+    /// Rolls register forward one byte, with `byte` appended to the window
+    /// and `out_entry` being local lookup table entry of the byte removed from it.
+    ///
+    /// See [`fill_rolling_table`](Crc::fill_rolling_table).
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub(crate) fn roll_register(register: u64, byte: u8, out_entry: u64) -> u64 {
+        update_register(register, Self::TABLE, A::PARAMS.refin, byte) ^ out_entry
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - CRATE
+
+/// Creates table for processing one byte at a time with [`update_register`].
+///
+/// # Panics
+///
+/// Panics if `params` are invalid.
+pub(crate) const fn make_table(params: &CrcParams) -> [u64; 256] {
+    assert!(params.width >= 1 && params.width <= 64, "invalid width");
+    let mask = width_mask(params.width);
+    assert!(params.poly & !mask == 0, "poly is wider than width");
+    assert!(params.init & !mask == 0, "init is wider than width");
+    assert!(params.xorout & !mask == 0, "xorout is wider than width");
+
+    let mut table = [0u64; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let mut crc;
+        let mut bit = 0;
+        if params.refin {
+            let poly = reflect(params.poly, params.width);
+            crc = byte as u64;
+            while bit < 8 {
+                crc = (crc >> 1) ^ (0u64.wrapping_sub(crc & 1) & poly);
+                bit += 1;
+            }
+        } else {
+            let poly = params.poly << (64 - params.width);
+            crc = (byte as u64) << 56;
+            while bit < 8 {
+                crc = (crc << 1) ^ (0u64.wrapping_sub(crc >> 63) & poly);
+                bit += 1;
+            }
+        }
+        table[byte] = crc;
+        byte += 1;
+    }
+    table
+}
+
+/// Creates tables for processing 8 bytes at a time, slicing-by-8.
+///
+/// # Panics
+///
+/// Panics if `params` are invalid or not reflected.
+pub(crate) const fn make_slicing_tables(params: &CrcParams) -> [[u64; 256]; 8] {
+    assert!(params.refin, "slicing tables require reflected algorithm");
+
+    let mut tables = [[0u64; 256]; 8];
+    tables[0] = make_table(params);
+    let mut n = 1;
+    while n < 8 {
+        let mut byte = 0;
+        while byte < 256 {
+            let prev = tables[n - 1][byte];
+            tables[n][byte] = (prev >> 8) ^ tables[0][prev as usize & 0xFF];
+            byte += 1;
+        }
+        n += 1;
+    }
+    tables
+}
+
+/// Fills lookup table from entries `0`, `1`, `2`, `4`, ..., `128`.
+///
+/// Table entries are affine in the byte, i.e. `table[a ^ b] = table[a] ^ table[b] ^ table[0]`,
+/// since CRC without init/xorout is linear in GF(2).
+/// So each entry is the XOR combination of entries of its one bits.
+///
+/// Complexity: `Θ(1)`
+pub(crate) fn fill_table_from_basis<T: Copy + BitXor<Output = T>>(table: &mut [T; 256]) {
+    for byte in 3..256usize {
+        let lowest_bit = byte & byte.wrapping_neg();
+        if lowest_bit != byte {
+            table[byte] = table[byte ^ lowest_bit] ^ table[lowest_bit] ^ table[0];
+        }
+    }
+}
+
+/// Creates table of `256 ** (2 ** n)` in Galois field for [`pow256`].
+pub(crate) const fn make_pow256_table(params: &CrcParams) -> [u64; USIZE_BITS] {
+    // `x ** 8`, reduced for widths up to 8
+    let mut x8 = 1;
+    let mut bit = 0;
+    while bit < 8 {
+        x8 = mul_x(x8, params);
+        bit += 1;
+    }
+
+    let mut table = [0u64; USIZE_BITS];
+    table[0] = x8;
+    let mut n = 1;
+    while n < USIZE_BITS {
+        table[n] = mul(table[n - 1], table[n - 1], params);
+        n += 1;
+    }
+    table
+}
+
+/// Computes `a * b` in Galois field, with unreflected values.
+///
+/// Complexity: `Θ(width)`
+pub(crate) const fn mul(a: u64, b: u64, params: &CrcParams) -> u64 {
+    let top = 1 << (params.width - 1);
+    let mut product = 0;
+    let mut bit = top;
+    while bit != 0 {
+        product = mul_x(product, params);
+        if b & bit != 0 {
+            product ^= a;
+        }
+        bit >>= 1;
+    }
+    product
+}
+
+/// Converts register to unreflected value aligned to lowest bit.
+#[inline(always)]
+pub(crate) const fn register_to_normal(register: u64, params: &CrcParams) -> u64 {
+    if params.refin {
+        reflect(register, params.width)
+    } else {
+        register >> (64 - params.width)
+    }
+}
+
+/// Converts unreflected value aligned to lowest bit to register.
+///
+/// Register is reflected if `refin` is `true`,
+/// and otherwise aligned to highest bit.
+#[inline(always)]
+pub(crate) const fn normal_to_register(normal: u64, params: &CrcParams) -> u64 {
+    if params.refin {
+        reflect(normal, params.width)
+    } else {
+        normal << (64 - params.width)
+    }
+}
+
+/// Converts register to checksum.
+#[inline(always)]
+pub(crate) const fn register_to_checksum(register: u64, params: &CrcParams) -> u64 {
+    let normal = register_to_normal(register, params);
+    let output = if params.refout {
+        reflect(normal, params.width)
+    } else {
+        normal
+    };
+    output ^ params.xorout
+}
+
+/// Computes `256 ** power` in Galois field using exponentiation by squaring.
+///
+/// Complexity: `Θ(one_bits n)`
+pub(crate) fn pow256(mut power: usize, table: &[u64; USIZE_BITS], params: &CrcParams) -> u64 {
+    if power == 0 {
+        return 1;
+    }
+
+    // LOWEST ONE BIT
+
+    let mut pos = power.trailing_zeros() as usize;
+    let mut result = table[pos];
+    pos += 1;
+    power >>= pos;
+
+    // OTHER ONE BITS
+
+    while power > 0 {
+        if power & 1 == 1 {
+            result = mul(result, table[pos], params);
+        }
+        pos += 1;
+        power >>= 1;
+    }
+
+    result
+}
+
+#[inline(always)]
+pub(crate) fn update_register(register: u64, table: &[u64; 256], refin: bool, byte: u8) -> u64 {
+    if refin {
+        table[(register as u8 ^ byte) as usize] ^ (register >> 8)
+    } else {
+        table[((register >> 56) as u8 ^ byte) as usize] ^ (register << 8)
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+/// Computes `value * x` in Galois field, with unreflected values.
+const fn mul_x(value: u64, params: &CrcParams) -> u64 {
+    let carry = (value >> (params.width - 1)) & 1;
+    ((value << 1) & width_mask(params.width)) ^ (0u64.wrapping_sub(carry) & params.poly)
+}
+
+const fn reflect(value: u64, width: u32) -> u64 {
+    value.reverse_bits() >> (64 - width)
+}
+
+const fn width_mask(width: u32) -> u64 {
+    u64::MAX >> (64 - width)
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        catalogue::{Crc32Iscsi, Crc32IsoHdlc, Crc64Nvme, Crc64XzAlgo},
+        DualCrc,
+    };
+
+    const CHECK_DATA: &[u8] = b"123456789";
+
+    macro_rules! algorithm {
+        ($name:ident, $width:expr, $poly:expr, $init:expr, $refin:expr, $refout:expr, $xorout:expr) => {
+            struct $name;
+
+            impl CrcAlgorithm for $name {
+                const PARAMS: CrcParams = CrcParams {
+                    width: $width,
+                    poly: $poly,
+                    init: $init,
+                    refin: $refin,
+                    refout: $refout,
+                    xorout: $xorout,
+                };
+            }
+        };
+    }
+
+    // unusual widths and reflections
+    algorithm!(Crc3Gsm, 3, 0x3, 0x0, false, false, 0x7);
+    algorithm!(Crc5Usb, 5, 0x05, 0x1F, true, true, 0x1F);
+    algorithm!(Crc12Umts, 12, 0x80F, 0x000, false, true, 0x000);
+    algorithm!(Crc16Ibm3740, 16, 0x1021, 0xFFFF, false, false, 0x0000);
+    algorithm!(Crc32Bzip2, 32, 0x04C11DB7, 0xFFFFFFFF, false, false, 0xFFFFFFFF);
+    algorithm!(
+        Crc64We,
+        64,
+        0x42F0E1EBA9EA3693,
+        0xFFFFFFFFFFFFFFFF,
+        false,
+        false,
+        0xFFFFFFFFFFFFFFFF
+    );
+
+    fn check_update_zeros<A: CrcAlgorithm>() {
+        for count in [0, 1, 2, 7, 8, 9, 100, 1000] {
+            let mut crc = Crc::<A>::new();
+            crc.update(b"abc");
+            crc.update_zeros(count);
+            crc.update(b"def");
+
+            let mut expected = Crc::<A>::new();
+            expected.update(b"abc");
+            for _ in 0..count {
+                expected.update([0]);
+            }
+            expected.update(b"def");
+
+            assert_eq!(crc.get(), expected.get(), "count {}", count);
+        }
+    }
+
+    // ============================================================
+    // "check" values from "Catalogue of parametrised CRC algorithms"
+
+    #[test]
+    fn check_catalogue() {
+        assert_eq!(Crc::<Crc32Iscsi>::checksum(CHECK_DATA), 0xE3069283);
+        assert_eq!(Crc::<Crc32IsoHdlc>::checksum(CHECK_DATA), 0xCBF43926);
+        assert_eq!(Crc::<Crc64Nvme>::checksum(CHECK_DATA), 0xAE8B14860A799888);
+        assert_eq!(Crc::<Crc64XzAlgo>::checksum(CHECK_DATA), 0x995DC9BBDF1939FA);
+    }
+
+    #[test]
+    fn check_other() {
+        assert_eq!(Crc::<Crc3Gsm>::checksum(CHECK_DATA), 0x4);
+        assert_eq!(Crc::<Crc5Usb>::checksum(CHECK_DATA), 0x19);
+        assert_eq!(Crc::<Crc12Umts>::checksum(CHECK_DATA), 0xDAF);
+        assert_eq!(Crc::<Crc16Ibm3740>::checksum(CHECK_DATA), 0x29B1);
+        assert_eq!(Crc::<Crc32Bzip2>::checksum(CHECK_DATA), 0xFC891918);
+        assert_eq!(Crc::<Crc64We>::checksum(CHECK_DATA), 0x62EC59E3F1A4F00A);
+    }
+
+    // ============================================================
+    // Crc
+
+    #[test]
+    fn empty() {
+        assert_eq!(Crc::<Crc32IsoHdlc>::checksum(b""), 0);
+        assert_eq!(Crc::<Crc16Ibm3740>::checksum(b""), 0xFFFF);
+    }
+
+    #[test]
+    fn same_as_dual_crc() {
+        let data = b"The quick brown fox jumps over the lazy dog.";
+        assert_eq!(
+            (
+                Crc::<Crc32Iscsi>::checksum(data) as u32,
+                Crc::<Crc64XzAlgo>::checksum(data)
+            ),
+            DualCrc::checksum(data)
+        );
+    }
+
+    #[test]
+    fn update_zeros() {
+        check_update_zeros::<Crc3Gsm>();
+        check_update_zeros::<Crc5Usb>();
+        check_update_zeros::<Crc12Umts>();
+        check_update_zeros::<Crc32Iscsi>();
+        check_update_zeros::<Crc32Bzip2>();
+        check_update_zeros::<Crc64XzAlgo>();
+        check_update_zeros::<Crc64We>();
+    }
+}
@@ -0,0 +1,157 @@
+use digest::{
+    consts::{U4, U8},
+    FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+};
+
+use crate::{Crc32c, Crc64Xz};
+
+// ======================================================================
+// Crc32c - IMPL digest
+
+impl OutputSizeUser for Crc32c {
+    type OutputSize = U4;
+}
+
+impl Update for Crc32c {
+    fn update(&mut self, data: &[u8]) {
+        Crc32c::update(self, data);
+    }
+}
+
+impl FixedOutput for Crc32c {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.get().to_be_bytes());
+    }
+}
+
+impl FixedOutputReset for Crc32c {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.get().to_be_bytes());
+        self.reset();
+    }
+}
+
+impl Reset for Crc32c {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl HashMarker for Crc32c {}
+
+// ======================================================================
+// Crc64Xz - IMPL digest
+
+impl OutputSizeUser for Crc64Xz {
+    type OutputSize = U8;
+}
+
+impl Update for Crc64Xz {
+    fn update(&mut self, data: &[u8]) {
+        Crc64Xz::update(self, data);
+    }
+}
+
+impl FixedOutput for Crc64Xz {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.get().to_be_bytes());
+    }
+}
+
+impl FixedOutputReset for Crc64Xz {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.get().to_be_bytes());
+        self.reset();
+    }
+}
+
+impl Reset for Crc64Xz {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl HashMarker for Crc64Xz {}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use digest::Digest;
+
+    use crate::DualCrc;
+
+    fn check<D: Digest>(data: &[u8]) -> Output<D> {
+        let mut digest = D::new();
+        Digest::update(&mut digest, data);
+        digest.finalize()
+    }
+
+    // ============================================================
+    // Crc32c
+
+    #[test]
+    fn crc32c_digest() {
+        // "check" value from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(check::<Crc32c>(b"123456789")[..], [0xE3, 0x06, 0x92, 0x83]);
+    }
+
+    #[test]
+    fn crc32c_reset() {
+        let mut digest = Crc32c::default();
+        Digest::update(&mut digest, b"abc");
+        assert_eq!(
+            digest.finalize_reset()[..],
+            DualCrc::checksum32(b"abc").to_be_bytes()
+        );
+        Digest::update(&mut digest, b"123456789");
+        assert_eq!(digest.finalize()[..], 0xE3069283u32.to_be_bytes());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn crc32c_dyn_digest() {
+        let mut digest: alloc::boxed::Box<dyn digest::DynDigest> =
+            alloc::boxed::Box::new(Crc32c::default());
+        digest.update(b"123456789");
+        assert_eq!(digest.output_size(), 4);
+        assert_eq!(digest.finalize()[..], 0xE3069283u32.to_be_bytes());
+    }
+
+    // ============================================================
+    // Crc64Xz
+
+    #[test]
+    fn crc64xz_digest() {
+        // "check" value from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(
+            check::<Crc64Xz>(b"123456789")[..],
+            0x995DC9BBDF1939FAu64.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn crc64xz_reset() {
+        let mut digest = Crc64Xz::default();
+        Digest::update(&mut digest, b"abc");
+        assert_eq!(
+            digest.finalize_reset()[..],
+            DualCrc::checksum64(b"abc").to_be_bytes()
+        );
+        Digest::update(&mut digest, b"123456789");
+        assert_eq!(digest.finalize()[..], 0x995DC9BBDF1939FAu64.to_be_bytes());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn crc64xz_dyn_digest() {
+        let mut digest: alloc::boxed::Box<dyn digest::DynDigest> =
+            alloc::boxed::Box::new(Crc64Xz::default());
+        digest.update(b"123456789");
+        assert_eq!(digest.output_size(), 8);
+        assert_eq!(digest.finalize()[..], 0x995DC9BBDF1939FAu64.to_be_bytes());
+    }
+}
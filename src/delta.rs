@@ -0,0 +1,402 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::{DualCrc, RollingDualCrc};
+
+// ======================================================================
+// Signature - PUBLIC
+
+/// Block checksums of base data for generating a [`Delta`], rsync-style.
+///
+/// Base data is split into blocks of `block_size` bytes
+/// (last block may be shorter) and `CRC-32C` and `CRC-64/XZ` checksums
+/// of each block are stored, with blocks indexed by `CRC-32C`.
+///
+/// Note that CRC is not a cryptographic hash, so a malicious party can
+/// easily create different data with same checksums. Verify the result
+/// of [`apply_delta`] with a cryptographic hash if that matters.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{apply_delta, Delta, DeltaOp, Signature};
+///
+/// let base = b"The quick brown fox jumps over the lazy dog.";
+/// let target = b"The quick red fox jumps over the lazy dog.";
+///
+/// let signature = Signature::new(base, 8);
+/// let delta = Delta::new(&signature, target);
+///
+/// assert_eq!(delta.ops()[0], DeltaOp::Copy { block: 0 });
+/// assert_eq!(apply_delta(base, &delta).unwrap(), target);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Signature {
+    block_size: usize,
+    blocks: Vec<(u32, u64)>,
+    last_block_len: usize,
+    index: HashMap<u32, Vec<usize>>,
+}
+
+impl Signature {
+    /// Returns size of the blocks.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns `(crc32, crc64)` checksums of each block.
+    pub fn blocks(&self) -> &[(u32, u64)] {
+        &self.blocks
+    }
+
+    /// Computes signature of given base data with given block size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    ///
+    /// See [`Signature`] for an example.
+    pub fn new<T: AsRef<[u8]>>(base: T, block_size: usize) -> Self {
+        if block_size == 0 {
+            panic!("block_size is zero");
+        }
+
+        let base = base.as_ref();
+
+        let blocks: Vec<_> = base.chunks(block_size).map(DualCrc::checksum).collect();
+        let last_block_len = match base.len() % block_size {
+            0 if base.is_empty() => 0,
+            0 => block_size,
+            len => len,
+        };
+
+        let mut index: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (block, (crc32, _)) in blocks.iter().enumerate() {
+            if block + 1 < blocks.len() || last_block_len == block_size {
+                index.entry(*crc32).or_default().push(block);
+            }
+        }
+
+        Self {
+            block_size,
+            blocks,
+            last_block_len,
+            index,
+        }
+    }
+}
+
+// ======================================================================
+// Signature - PRIVATE
+
+impl Signature {
+    /// Returns full-size block with given checksums, if any.
+    fn find_block(&self, (crc32, crc64): (u32, u64)) -> Option<usize> {
+        self.index
+            .get(&crc32)?
+            .iter()
+            .copied()
+            .find(|block| self.blocks[*block].1 == crc64)
+    }
+}
+
+// ======================================================================
+// DeltaOp - PUBLIC
+
+/// Operation of a [`Delta`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeltaOp {
+    /// Copy block with given index from base data.
+    Copy {
+        /// Index of the block.
+        block: usize,
+    },
+    /// Append given bytes.
+    Literal {
+        /// Bytes to append.
+        bytes: Vec<u8>,
+    },
+}
+
+// ======================================================================
+// Delta - PUBLIC
+
+/// Operations which reconstruct target data from base data, rsync-style.
+///
+/// [`new`] rolls a [`RollingDualCrc`] window of `block_size` bytes
+/// through the target data, looking up `CRC-32C` of each window
+/// in the [`Signature`] of the base data and confirming with `CRC-64/XZ`.
+/// Matching windows become [`DeltaOp::Copy`] and other data [`DeltaOp::Literal`].
+///
+/// See [`Signature`] for an example.
+///
+/// [`new`]: Delta::new
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Delta {
+    block_size: usize,
+    ops: Vec<DeltaOp>,
+}
+
+impl Delta {
+    /// Returns size of the blocks referred by [`DeltaOp::Copy`].
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Unwraps this [`Delta`], returning the operations.
+    pub fn into_ops(self) -> Vec<DeltaOp> {
+        self.ops
+    }
+
+    /// Generates delta which reconstructs `target` from base data
+    /// of given [`Signature`].
+    ///
+    /// Consecutive literal bytes are always combined into one [`DeltaOp::Literal`].
+    ///
+    /// See [`Signature`] for an example.
+    pub fn new<T: AsRef<[u8]>>(signature: &Signature, target: T) -> Self {
+        let target = target.as_ref();
+        let block_size = signature.block_size;
+
+        let mut ops = Vec::new();
+        let mut literal_start = 0;
+
+        if target.len() >= block_size && signature.last_block_len > 0 {
+            // window is `target[pos..pos + block_size]`
+            let mut pos = 0;
+            let mut crc = RollingDualCrc::new(&target[..block_size]);
+
+            loop {
+                if let Some(block) = signature.find_block(crc.get()) {
+                    push_literal(&mut ops, &target[literal_start..pos]);
+                    ops.push(DeltaOp::Copy { block });
+
+                    pos += block_size;
+                    literal_start = pos;
+                    if pos + block_size > target.len() {
+                        break;
+                    }
+                    crc.roll_slice(&target[pos..pos + block_size]);
+                } else {
+                    if pos + block_size == target.len() {
+                        break;
+                    }
+                    crc.roll(target[pos + block_size]);
+                    pos += 1;
+                }
+            }
+        }
+
+        // last block of base data may be shorter than `block_size`
+        let last_block_len = signature.last_block_len;
+        let mut literal_end = target.len();
+        if last_block_len > 0
+            && last_block_len < block_size
+            && target.len() - literal_start >= last_block_len
+            && DualCrc::checksum(&target[target.len() - last_block_len..])
+                == signature.blocks[signature.blocks.len() - 1]
+        {
+            literal_end -= last_block_len;
+        }
+
+        push_literal(&mut ops, &target[literal_start..literal_end]);
+        if literal_end < target.len() {
+            ops.push(DeltaOp::Copy {
+                block: signature.blocks.len() - 1,
+            });
+        }
+
+        Self { block_size, ops }
+    }
+
+    /// Returns the operations.
+    pub fn ops(&self) -> &[DeltaOp] {
+        &self.ops
+    }
+}
+
+// ======================================================================
+// ApplyDeltaError - PUBLIC
+
+/// Error returned by [`apply_delta`] when [`Delta`] doesn't match base data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApplyDeltaError {
+    block: usize,
+}
+
+impl ApplyDeltaError {
+    /// Returns index of the block which is missing from base data.
+    pub fn block(&self) -> usize {
+        self.block
+    }
+}
+
+impl fmt::Display for ApplyDeltaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "block {} is not in base data", self.block)
+    }
+}
+
+impl Error for ApplyDeltaError {}
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Reconstructs target data by applying given [`Delta`] to base data.
+///
+/// # Errors
+///
+/// Returns [`ApplyDeltaError`] if [`DeltaOp::Copy`] refers to a block
+/// which is not in base data.
+///
+/// See [`Signature`] for an example.
+pub fn apply_delta<T: AsRef<[u8]>>(base: T, delta: &Delta) -> Result<Vec<u8>, ApplyDeltaError> {
+    let base = base.as_ref();
+    let mut target = Vec::new();
+
+    for op in &delta.ops {
+        match op {
+            DeltaOp::Copy { block } => {
+                let start = block
+                    .checked_mul(delta.block_size)
+                    .filter(|start| *start < base.len())
+                    .ok_or(ApplyDeltaError { block: *block })?;
+                let end = base.len().min(start + delta.block_size);
+                target.extend_from_slice(&base[start..end]);
+            }
+            DeltaOp::Literal { bytes } => target.extend_from_slice(bytes),
+        }
+    }
+
+    Ok(target)
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+fn push_literal(ops: &mut Vec<DeltaOp>, bytes: &[u8]) {
+    if !bytes.is_empty() {
+        ops.push(DeltaOp::Literal {
+            bytes: bytes.to_vec(),
+        });
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::random_data;
+
+    fn roundtrip(base: &[u8], target: &[u8], block_size: usize) -> Delta {
+        let delta = Delta::new(&Signature::new(base, block_size), target);
+        assert_eq!(apply_delta(base, &delta).unwrap(), target);
+        delta
+    }
+
+    fn copy_count(delta: &Delta) -> usize {
+        delta
+            .ops()
+            .iter()
+            .filter(|op| matches!(op, DeltaOp::Copy { .. }))
+            .count()
+    }
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn zero_block_size() {
+        Signature::new(b"abc", 0);
+    }
+
+    // ============================================================
+    // Signature
+
+    #[test]
+    fn signature_blocks() {
+        let signature = Signature::new(b"abcdefgh", 3);
+        assert_eq!(signature.block_size(), 3);
+        assert_eq!(
+            signature.blocks(),
+            [
+                DualCrc::checksum(b"abc"),
+                DualCrc::checksum(b"def"),
+                DualCrc::checksum(b"gh"),
+            ]
+        );
+    }
+
+    // ============================================================
+    // Delta / apply_delta
+
+    #[test]
+    fn delta_identical() {
+        let data = random_data(10_000);
+        let delta = roundtrip(&data, &data, 64);
+        let expected: Vec<_> = (0..157).map(|block| DeltaOp::Copy { block }).collect();
+        assert_eq!(delta.into_ops(), expected);
+    }
+
+    #[test]
+    fn delta_unrelated() {
+        let base = random_data(1000);
+        let target = b"Hello, world!".repeat(100);
+        let delta = roundtrip(&base, &target, 64);
+        assert_eq!(delta.ops(), [DeltaOp::Literal { bytes: target }]);
+    }
+
+    #[test]
+    fn delta_empty() {
+        assert_eq!(roundtrip(b"", b"", 4).ops(), []);
+        assert_eq!(roundtrip(b"abc", b"", 4).ops(), []);
+        assert_eq!(
+            roundtrip(b"", b"abc", 4).ops(),
+            [DeltaOp::Literal {
+                bytes: b"abc".to_vec()
+            }]
+        );
+    }
+
+    #[test]
+    fn delta_insert_and_remove() {
+        let base = random_data(10_000);
+
+        let mut target = base.clone();
+        target.splice(5000..5000, b"inserted".iter().copied());
+        target.drain(100..200);
+
+        let delta = roundtrip(&base, &target, 64);
+        // at most 4 blocks are affected by the two edits
+        assert!(copy_count(&delta) >= 157 - 4);
+    }
+
+    #[test]
+    fn delta_short_last_block() {
+        let delta = roundtrip(b"abcdefgh", b"xxabcdefgh", 3);
+        assert_eq!(
+            delta.ops(),
+            [
+                DeltaOp::Literal {
+                    bytes: b"xx".to_vec()
+                },
+                DeltaOp::Copy { block: 0 },
+                DeltaOp::Copy { block: 1 },
+                DeltaOp::Copy { block: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_delta_missing_block() {
+        let delta = Delta::new(&Signature::new(b"abcdefgh", 4), b"efgh");
+        assert_eq!(delta.ops(), [DeltaOp::Copy { block: 1 }]);
+        assert_eq!(
+            apply_delta(b"abcd", &delta),
+            Err(ApplyDeltaError { block: 1 })
+        );
+    }
+}
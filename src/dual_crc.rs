@@ -1,4 +1,6 @@
-use crate::{tables, Zeros};
+use core::hash::{BuildHasher, Hasher};
+
+use crate::{tables, Crc32c, Crc64Xz, Zeros};
 
 // ======================================================================
 // DualCrc - PUBLIC
@@ -47,6 +49,41 @@ pub struct DualCrc {
 }
 
 impl DualCrc {
+    /// Continues checksums computation with data whose checksums
+    /// have been computed separately into `other`.
+    ///
+    /// This is equivalent to [`update`] with the data of `other`,
+    /// but doesn't need that data, only its length `other_len`.
+    ///
+    /// Complexity: `Θ(one_bits other_len)` time
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::DualCrc;
+    ///
+    /// let mut crc = DualCrc::new();
+    /// crc.update("Hello");
+    ///
+    /// let mut other = DualCrc::new();
+    /// other.update(", world!");
+    ///
+    /// crc.append(&other, 8);
+    /// // checksums of "Hello, world!"
+    /// assert_eq!(crc.get(), (0xC8A106E5, 0x8E59E143665877C4));
+    /// ```
+    ///
+    /// [`update`]: DualCrc::update
+    pub fn append(&mut self, other: &DualCrc, other_len: usize) {
+        // Checksum of `A + B` is checksum of `A` followed by `len(B)` `0u8`:s
+        // XORed with checksum of `B`, as CRC without init/xorout is linear.
+        let zeros = Zeros::new(other_len);
+        self.inverted_crc32 =
+            zeros.apply_to_inverted_crc32(!self.inverted_crc32) ^ other.inverted_crc32;
+        self.inverted_crc64 =
+            zeros.apply_to_inverted_crc64(!self.inverted_crc64) ^ other.inverted_crc64;
+    }
+
     /// Computes 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums of given data.
     ///
     /// # Examples
@@ -60,42 +97,82 @@ impl DualCrc {
         (Self::checksum32(&data), Self::checksum64(&data))
     }
 
-    /// Computes 32-bit `CRC-32C` checksum of given data.
+    /// Computes 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums of given data
+    /// using multiple threads.
+    ///
+    /// Data is split into `threads` pieces of roughly equal size,
+    /// checksums of each piece are computed in separate thread
+    /// and then combined with [`append`].
+    /// Result is always same as with [`checksum`].
+    ///
+    /// Requires feature `parallel`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use rolling_dual_crc::DualCrc;
     ///
-    /// assert_eq!(DualCrc::checksum32("Hello, world!"), 0xC8A106E5);
+    /// assert_eq!(
+    ///     DualCrc::checksum_parallel("Hello, world!", 4),
+    ///     (0xC8A106E5, 0x8E59E143665877C4)
+    /// );
     /// ```
-    pub fn checksum32<T: AsRef<[u8]>>(data: T) -> u32 {
-        #[cfg(feature = "crc32c")]
-        return crc32c::crc32c(data.as_ref());
+    ///
+    /// [`append`]: DualCrc::append
+    /// [`checksum`]: DualCrc::checksum
+    #[cfg(feature = "parallel")]
+    pub fn checksum_parallel<T: AsRef<[u8]>>(data: T, threads: usize) -> (u32, u64) {
+        if threads == 0 {
+            panic!("threads is zero");
+        }
 
-        #[cfg(not(feature = "crc32c"))]
-        {
-            let mut inverted_crc: u32 = !0;
+        let data = data.as_ref();
+        if threads == 1 || data.len() < threads {
+            return Self::checksum(data);
+        }
 
-            let data = data.as_ref();
-            let mut pos = 0;
-            let mut remaining = data.len();
+        let piece_size = data.len().div_ceil(threads);
+        let mut pieces = data.chunks(piece_size);
+        let first = pieces.next().unwrap();
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = pieces
+                .map(|piece| {
+                    scope.spawn(move || {
+                        let mut crc = DualCrc::new();
+                        crc.update(piece);
+                        (crc, piece.len())
+                    })
+                })
+                .collect();
 
-            while remaining >= 8 {
-                inverted_crc =
-                    tables::update_inverted_crc32_8bytes(inverted_crc, &data[pos..pos + 8]);
-                pos += 8;
-                remaining -= 8;
-            }
+            let mut crc = DualCrc::new();
+            crc.update(first);
 
-            while remaining > 0 {
-                inverted_crc = tables::update_inverted_crc32(inverted_crc, data[pos]);
-                pos += 1;
-                remaining -= 1;
+            for handle in handles {
+                let (other, other_len) = handle.join().unwrap();
+                crc.append(&other, other_len);
             }
 
-            !inverted_crc
-        }
+            crc.get()
+        })
+    }
+
+    /// Computes 32-bit `CRC-32C` checksum of given data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::DualCrc;
+    ///
+    /// assert_eq!(DualCrc::checksum32("Hello, world!"), 0xC8A106E5);
+    /// ```
+    pub fn checksum32<T: AsRef<[u8]>>(data: T) -> u32 {
+        Crc32c::checksum(data)
     }
 
     /// Computes 64-bit `CRC-64/XZ` checksum of given data.
@@ -108,36 +185,37 @@ impl DualCrc {
     /// assert_eq!(DualCrc::checksum64("Hello, world!"), 0x8E59E143665877C4);
     /// ```
     pub fn checksum64<T: AsRef<[u8]>>(data: T) -> u64 {
-        #[cfg(feature = "crc64fast")]
-        {
-            let mut crc = crc64fast::Digest::new();
-            crc.write(data.as_ref());
-            crc.sum64()
-        }
-
-        #[cfg(not(feature = "crc64fast"))]
-        {
-            let mut inverted_crc: u64 = !0;
-
-            let data = data.as_ref();
-            let mut pos = 0;
-            let mut remaining = data.len();
-
-            while remaining >= 8 {
-                inverted_crc =
-                    tables::update_inverted_crc64_8bytes(inverted_crc, &data[pos..pos + 8]);
-                pos += 8;
-                remaining -= 8;
-            }
-
-            while remaining > 0 {
-                inverted_crc = tables::update_inverted_crc64(inverted_crc, data[pos]);
-                pos += 1;
-                remaining -= 1;
-            }
+        Crc64Xz::checksum(data)
+    }
 
-            !inverted_crc
-        }
+    /// Combines checksums of two separately processed pieces of data
+    /// into checksums of their concatenation.
+    ///
+    /// This is equivalent to [`append`] with a copy of `a`.
+    ///
+    /// Complexity: `Θ(one_bits len_b)` time
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::DualCrc;
+    ///
+    /// let mut a = DualCrc::new();
+    /// a.update("Hello");
+    ///
+    /// let mut b = DualCrc::new();
+    /// b.update(", world!");
+    ///
+    /// // checksums of "Hello, world!"
+    /// let crc = DualCrc::combine(&a, &b, 8);
+    /// assert_eq!(crc.get(), (0xC8A106E5, 0x8E59E143665877C4));
+    /// ```
+    ///
+    /// [`append`]: DualCrc::append
+    pub fn combine(a: &DualCrc, b: &DualCrc, len_b: usize) -> DualCrc {
+        let mut crc = *a;
+        crc.append(b, len_b);
+        crc
     }
 
     /// Returns 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums
@@ -285,6 +363,75 @@ impl Default for DualCrc {
     }
 }
 
+// ======================================================================
+// DualCrc - IMPL Hasher
+
+/// [`finish`] returns 64-bit `CRC-64/XZ` checksum of the data written so far.
+///
+/// Since `CRC-64/XZ` is a fixed standard algorithm, the checksum of
+/// given bytes will remain same in all future versions of this crate.
+/// However note that [`Hash`] implementations of many standard types
+/// write additional bytes (e.g. `str` writes terminating `0xFF`)
+/// and integers are written in native byte order,
+/// so persisted hashes are portable only when written bytes are.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::DualCrc;
+/// use std::hash::Hasher;
+///
+/// let mut hasher = DualCrc::new();
+/// hasher.write(b"Hello, world!");
+/// assert_eq!(hasher.finish(), 0x8E59E143665877C4);
+/// ```
+///
+/// [`finish`]: Hasher::finish
+/// [`Hash`]: core::hash::Hash
+impl Hasher for DualCrc {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.get64()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+// ======================================================================
+// BuildDualCrcHasher - PUBLIC
+
+/// [`BuildHasher`] for using [`DualCrc`] as a deterministic [`Hasher`],
+/// e.g. with [`HashMap`].
+///
+/// See [`DualCrc`] implementation of [`Hasher`] for stability of hashes.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::BuildDualCrcHasher;
+/// use std::collections::HashMap;
+///
+/// let mut map: HashMap<&[u8], u32, _> = HashMap::with_hasher(BuildDualCrcHasher);
+/// map.insert(b"Hello", 1);
+/// map.insert(b"world", 2);
+/// assert_eq!(map[&b"world"[..]], 2);
+/// ```
+///
+/// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildDualCrcHasher;
+
+impl BuildHasher for BuildDualCrcHasher {
+    type Hasher = DualCrc;
+
+    fn build_hasher(&self) -> DualCrc {
+        DualCrc::new()
+    }
+}
+
 // ======================================================================
 // TESTS
 
@@ -316,6 +463,67 @@ mod tests {
         (b"abcdefghijklmno", (0xBF1A2C62, 0xC84B31ADFD591E7E)),
     ];
 
+    // ============================================================
+    // append / combine
+
+    #[test]
+    fn append_all_splits() {
+        let data = b"abcdefghijklmno";
+        for split in 0..=data.len() {
+            let mut crc = DualCrc::new();
+            crc.update(&data[..split]);
+
+            let mut other = DualCrc::new();
+            other.update(&data[split..]);
+
+            crc.append(&other, data.len() - split);
+            assert_eq!(crc.get(), TESTDATA_0_TO_15[15].1);
+        }
+    }
+
+    #[test]
+    fn append_with_zeros() {
+        let mut crc = DualCrc::new();
+        crc.update(b"abc");
+
+        let mut other = DualCrc::new();
+        other.update_with_zeros(&Zeros::new(123));
+        other.update(b"def");
+        other.update_with_zeros(&Zeros::new(456));
+
+        crc.append(&other, 123 + 3 + 456);
+        // same as in `update_with_zeros_mixed`
+        assert_eq!(crc.get(), (0x5B8D8166, 0xA9B8E3BFC470CB4D));
+    }
+
+    #[test]
+    fn combine() {
+        let mut a = DualCrc::new();
+        a.update(b"123");
+
+        let mut b = DualCrc::new();
+        b.update(b"456789");
+
+        // "check" values from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(
+            DualCrc::combine(&a, &b, 6).get(),
+            (0xE3069283, 0x995DC9BBDF1939FA)
+        );
+        // `a` and `b` are unchanged
+        assert_eq!(a.get(), DualCrc::checksum(b"123"));
+        assert_eq!(b.get(), DualCrc::checksum(b"456789"));
+    }
+
+    #[test]
+    fn combine_empty() {
+        let mut a = DualCrc::new();
+        a.update(b"abc");
+
+        let empty = DualCrc::new();
+        assert_eq!(DualCrc::combine(&a, &empty, 0).get(), a.get());
+        assert_eq!(DualCrc::combine(&empty, &a, 3).get(), a.get());
+    }
+
     // ============================================================
     // checksum
 
@@ -326,6 +534,36 @@ mod tests {
         }
     }
 
+    // ============================================================
+    // checksum_parallel
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn checksum_parallel() {
+        for (input, expected) in TESTDATA_0_TO_15 {
+            for threads in 1..=5 {
+                assert_eq!(DualCrc::checksum_parallel(input, threads), expected);
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn checksum_parallel_large() {
+        let data: Vec<u8> = (0..100_003u32).map(|x| (x * 7 + x / 256) as u8).collect();
+        let expected = DualCrc::checksum(&data);
+        for threads in [1, 2, 3, 8, 64] {
+            assert_eq!(DualCrc::checksum_parallel(&data, threads), expected);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    #[should_panic]
+    fn checksum_parallel_zero_threads() {
+        DualCrc::checksum_parallel(b"abc", 0);
+    }
+
     // ============================================================
     // checksum32
 
@@ -346,6 +584,29 @@ mod tests {
         }
     }
 
+    // ============================================================
+    // Hasher / BuildDualCrcHasher
+
+    #[test]
+    fn hasher_write_finish() {
+        let mut hasher = BuildDualCrcHasher.build_hasher();
+        hasher.write(b"123456789");
+        // "check" value from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(hasher.finish(), 0x995DC9BBDF1939FA);
+        hasher.write(b"abc");
+        // This value has been confirmed with `crc` crate
+        assert_eq!(hasher.finish(), 0x5A062275250CB126);
+    }
+
+    #[test]
+    fn hasher_hash_one() {
+        // `u32` is hashed as its native-endian bytes
+        assert_eq!(
+            BuildDualCrcHasher.hash_one(0x64636261u32),
+            DualCrc::checksum64(0x64636261u32.to_ne_bytes())
+        );
+    }
+
     // ============================================================
     // new
 
@@ -429,10 +690,10 @@ mod tests {
             (0x530ED410, 0x3FC1C24BBCAE428D),
         ];
 
-        for n in 0..EXPECTED.len() {
+        for (n, expected) in EXPECTED.iter().enumerate() {
             let mut crc = DualCrc::new();
             crc.update_with_zeros(&Zeros::new(n));
-            assert_eq!(crc.get(), EXPECTED[n]);
+            assert_eq!(crc.get(), *expected);
         }
     }
 
@@ -471,10 +732,10 @@ mod tests {
             (0x527D5351, 0xF15374CE0B53F6C1), // 2^31 `0u8`:s
         ];
 
-        for n in 0..EXPECTED.len() {
+        for (n, expected) in EXPECTED.iter().enumerate() {
             let mut crc = DualCrc::new();
             crc.update_with_zeros(&Zeros::new(2usize.pow(n as u32 + 4)));
-            assert_eq!(crc.get(), EXPECTED[n]);
+            assert_eq!(crc.get(), *expected);
         }
     }
 
@@ -0,0 +1,356 @@
+use alloc::vec::Vec;
+
+use crate::{DualCrc, RollingDualCrc, RollingTables};
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Returns offset of first occurrence of `needle` in `haystack`,
+/// or `None` if there is none.
+///
+/// This is Rabin-Karp search using [`RollingDualCrc`], see [`Finder`] for details.
+/// Use [`Finder`] instead when searching same needle from many haystacks.
+///
+/// Empty `needle` is found at offset `0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::find;
+///
+/// assert_eq!(find("Hello, world!", "world"), Some(7));
+/// assert_eq!(find("Hello, world!", "xyz"), None);
+/// ```
+pub fn find<H: AsRef<[u8]>, N: AsRef<[u8]>>(haystack: H, needle: N) -> Option<usize> {
+    find_iter(haystack.as_ref(), needle.as_ref()).next()
+}
+
+/// Returns iterator over offsets of non-overlapping occurrences
+/// of `needle` in `haystack`, in ascending order.
+///
+/// This is Rabin-Karp search using [`RollingDualCrc`], see [`Finder`] for details.
+/// Use [`Finder`] instead when searching same needle from many haystacks.
+///
+/// Empty `needle` is found at every offset `0 ..= haystack.len()`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::find_iter;
+///
+/// let offsets: Vec<usize> = find_iter(b"abcabcab", b"ab").collect();
+/// assert_eq!(offsets, [0, 3, 6]);
+///
+/// // non-overlapping
+/// let offsets: Vec<usize> = find_iter(b"aaaaa", b"aa").collect();
+/// assert_eq!(offsets, [0, 2]);
+/// ```
+pub fn find_iter<'a>(haystack: &'a [u8], needle: &'a [u8]) -> FindIter<'a> {
+    let tables = if needle.is_empty() || needle.len() > haystack.len() {
+        None
+    } else {
+        Some(RollingTables::new(needle.len()))
+    };
+    FindIter::new(haystack, needle, DualCrc::checksum(needle), tables, true)
+}
+
+// ======================================================================
+// Finder - PUBLIC
+
+/// Searches occurrences of a needle in haystacks using [`RollingDualCrc`],
+/// i.e. Rabin-Karp search.
+///
+/// - Checksums of the needle are computed once.
+/// - Window of needle size is rolled through the haystack.
+/// - Where both checksums of the window equal those of the needle,
+///   the window is a candidate, which is then confirmed by comparing bytes.
+///
+/// Search takes `Θ(n)` time, where `n` is size of the haystack,
+/// regardless of the size of the needle.
+///
+/// Local lookup tables of [`RollingDualCrc`] are built once by [`new`]
+/// and shared by all searches.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::Finder;
+///
+/// let finder = Finder::new("needle");
+///
+/// assert_eq!(finder.find("haystack with a needle"), Some(16));
+/// assert_eq!(finder.find("haystack without one"), None);
+///
+/// let offsets: Vec<usize> = finder.find_iter(b"needle, needle").collect();
+/// assert_eq!(offsets, [0, 8]);
+/// ```
+///
+/// [`new`]: Finder::new
+#[derive(Clone, Debug)]
+pub struct Finder {
+    needle: Vec<u8>,
+    checksum: (u32, u64),
+    tables: Option<RollingTables>,
+    verify: bool,
+}
+
+impl Finder {
+    /// Returns offset of first occurrence of the needle in `haystack`,
+    /// or `None` if there is none.
+    ///
+    /// See [`Finder`] for an example.
+    pub fn find<T: AsRef<[u8]>>(&self, haystack: T) -> Option<usize> {
+        self.find_iter(haystack.as_ref()).next()
+    }
+
+    /// Returns iterator over offsets of non-overlapping occurrences
+    /// of the needle in `haystack`, in ascending order.
+    ///
+    /// See [`Finder`] for an example.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> FindIter<'a> {
+        FindIter::new(
+            haystack,
+            &self.needle,
+            self.checksum,
+            self.tables.clone(),
+            self.verify,
+        )
+    }
+
+    /// Returns the needle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::Finder;
+    ///
+    /// assert_eq!(Finder::new("abc").needle(), b"abc");
+    /// ```
+    pub fn needle(&self) -> &[u8] {
+        &self.needle
+    }
+
+    /// Creates a new [`Finder`] for given needle.
+    ///
+    /// - Copies the needle.
+    /// - Computes checksums of the needle.
+    /// - Allocates and initializes local lookup tables (3 kiB total)
+    ///   unless the needle is empty.
+    ///
+    /// Empty needle is found at every offset of the haystack
+    /// including its end.
+    ///
+    /// See [`Finder`] for an example.
+    pub fn new<T: AsRef<[u8]>>(needle: T) -> Self {
+        let needle = needle.as_ref();
+        Self {
+            needle: needle.to_vec(),
+            checksum: DualCrc::checksum(needle),
+            tables: (!needle.is_empty()).then(|| RollingTables::new(needle.len())),
+            verify: true,
+        }
+    }
+
+    /// Sets whether candidates are confirmed by comparing bytes (default `true`).
+    ///
+    /// Without byte comparison a window is reported as an occurrence when
+    /// both its 32-bit and 64-bit checksums equal those of the needle.
+    /// For random data a false match is then very unlikely,
+    /// but may still happen and can be crafted deliberately.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::Finder;
+    ///
+    /// let mut finder = Finder::new("needle");
+    /// finder.set_verify(false);
+    /// assert_eq!(finder.find("haystack with a needle"), Some(16));
+    /// ```
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify = verify;
+    }
+}
+
+// ======================================================================
+// FindIter - PUBLIC
+
+/// Iterator over offsets of occurrences of a needle in a haystack.
+///
+/// This is created by [`find_iter`] and [`Finder::find_iter`].
+///
+/// [`find_iter`]: crate::find_iter
+#[derive(Clone, Debug)]
+pub struct FindIter<'a> {
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    checksum: (u32, u64),
+    verify: bool,
+
+    /// Offset of the current window.
+    pos: usize,
+    /// Current window, `None` when search is done or needle is empty.
+    crc: Option<RollingDualCrc>,
+}
+
+// ======================================================================
+// FindIter - PRIVATE
+
+impl<'a> FindIter<'a> {
+    fn new(
+        haystack: &'a [u8],
+        needle: &'a [u8],
+        checksum: (u32, u64),
+        tables: Option<RollingTables>,
+        verify: bool,
+    ) -> Self {
+        let crc = match tables {
+            Some(tables) if needle.len() <= haystack.len() => Some(RollingDualCrc::with_tables(
+                tables,
+                &haystack[..needle.len()],
+            )),
+            _ => None,
+        };
+
+        Self {
+            haystack,
+            needle,
+            checksum,
+            verify,
+
+            pos: 0,
+            crc,
+        }
+    }
+}
+
+// ======================================================================
+// FindIter - IMPL Iterator
+
+impl<'a> Iterator for FindIter<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.needle.len();
+
+        if len == 0 {
+            if self.pos > self.haystack.len() {
+                return None;
+            }
+            self.pos += 1;
+            return Some(self.pos - 1);
+        }
+
+        let crc = self.crc.as_mut()?;
+        loop {
+            let pos = self.pos;
+
+            if crc.get() == self.checksum
+                && (!self.verify || &self.haystack[pos..pos + len] == self.needle)
+            {
+                // Continue after the occurrence with new window
+                // instead of rolling through the occurrence.
+                let tables = crc.tables().clone();
+                self.pos = pos + len;
+                self.crc = self
+                    .haystack
+                    .get(pos + len..pos + 2 * len)
+                    .map(|window| RollingDualCrc::with_tables(tables, window));
+                return Some(pos);
+            }
+
+            if pos + len == self.haystack.len() {
+                self.crc = None;
+                return None;
+            }
+
+            crc.roll(self.haystack[pos + len]);
+            self.pos += 1;
+        }
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::random_data;
+
+    /// Returns offsets of non-overlapping occurrences by naive search.
+    fn naive_find_all(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+        let mut offsets = Vec::new();
+        let mut pos = 0;
+        while pos + needle.len() <= haystack.len() {
+            if &haystack[pos..pos + needle.len()] == needle {
+                offsets.push(pos);
+                pos += needle.len().max(1);
+            } else {
+                pos += 1;
+            }
+        }
+        offsets
+    }
+
+    // ============================================================
+    // find / find_iter
+
+    #[test]
+    fn find_iter_same_as_naive() {
+        let mut haystack = random_data(10_000);
+        let needle = haystack[1234..1234 + 100].to_vec();
+        haystack[5000..5100].copy_from_slice(&needle);
+        haystack[9900..].copy_from_slice(&needle);
+
+        let offsets: Vec<usize> = find_iter(&haystack, &needle).collect();
+        assert_eq!(offsets, [1234, 5000, 9900]);
+        assert_eq!(offsets, naive_find_all(&haystack, &needle));
+
+        for needle in [&b"\x00"[..], b"ab", &haystack[..1], &haystack[9999..]] {
+            let offsets: Vec<usize> = find_iter(&haystack, needle).collect();
+            assert_eq!(offsets, naive_find_all(&haystack, needle));
+        }
+    }
+
+    #[test]
+    fn find_iter_non_overlapping() {
+        let offsets: Vec<usize> = find_iter(b"aaaaaaa", b"aaa").collect();
+        assert_eq!(offsets, [0, 3]);
+        let offsets: Vec<usize> = find_iter(b"abababab", b"abab").collect();
+        assert_eq!(offsets, [0, 4]);
+    }
+
+    #[test]
+    fn find_edge_cases() {
+        assert_eq!(find("abc", "abc"), Some(0));
+        assert_eq!(find("abc", "abcd"), None);
+        assert_eq!(find("", "a"), None);
+        assert_eq!(find("abc", ""), Some(0));
+        assert_eq!(find("", ""), Some(0));
+
+        let offsets: Vec<usize> = find_iter(b"ab", b"").collect();
+        assert_eq!(offsets, [0, 1, 2]);
+    }
+
+    // ============================================================
+    // Finder
+
+    #[test]
+    fn finder_many_haystacks() {
+        let haystack = random_data(1000);
+        let needle = &haystack[500..520];
+
+        let mut finder = Finder::new(needle);
+        assert_eq!(finder.needle(), needle);
+        assert_eq!(finder.find(&haystack), Some(500));
+        assert_eq!(finder.find(&haystack[..519]), None);
+        assert_eq!(finder.find(&haystack[501..]), None);
+        assert_eq!(finder.find(&haystack[490..]), Some(10));
+
+        finder.set_verify(false);
+        assert_eq!(finder.find(&haystack), Some(500));
+        assert_eq!(finder.find(&haystack[501..]), None);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{rolling_dual_crc, DualCrc};
+
+// ======================================================================
+// InlineRollingDualCrc - PUBLIC
+
+/// Computes 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums
+/// in a rolling window of `N` bytes without any heap allocations.
+///
+/// This works like [`RollingDualCrc`], but window size is fixed at compile time
+/// and both the window contents and local lookup tables (3 kiB total)
+/// are stored inline, so this is available without `alloc` feature.
+///
+/// # Examples
+///
+/// Compute checksums of 3-byte windows of `"abcde"`,
+/// i.e. `"abc"`, `"bcd"` and `"cde"`.
+///
+/// ```rust
+/// use rolling_dual_crc::InlineRollingDualCrc;
+///
+/// let mut crc = InlineRollingDualCrc::new(*b"abc");
+///
+/// // checksum of "abc"
+/// assert_eq!(crc.get32(), 0x364B3FB7);
+///
+/// crc.roll(b'd');
+/// // checksum of "bcd"
+/// assert_eq!(crc.get32(), 0x1B0D0358);
+///
+/// crc.roll(b'e');
+/// // checksum of "cde"
+/// assert_eq!(crc.get32(), 0x364ADB60);
+/// ```
+///
+#[cfg_attr(feature = "alloc", doc = "[`RollingDualCrc`]: crate::RollingDualCrc")]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "[`RollingDualCrc`]: https://docs.rs/rolling-dual-crc/0.1.0/rolling_dual_crc/struct.RollingDualCrc.html"
+)]
+#[derive(Clone, Debug)]
+pub struct InlineRollingDualCrc<const N: usize> {
+    inverted_crc32: u32,
+    inverted_crc64: u64,
+
+    start_pos: usize,
+    data: [u8; N],
+
+    table32: [u32; 256],
+    table64: [u64; 256],
+}
+
+impl<const N: usize> InlineRollingDualCrc<N> {
+    /// Returns 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums
+    /// of the current window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::InlineRollingDualCrc;
+    ///
+    /// let mut crc = InlineRollingDualCrc::new(*b"abc");
+    /// crc.roll(b'd');
+    /// // checksums of "bcd"
+    /// assert_eq!(crc.get(), (0x1B0D0358, 0x0557EA6AA1219070));
+    /// ```
+    #[inline(always)]
+    pub fn get(&self) -> (u32, u64) {
+        (!self.inverted_crc32, !self.inverted_crc64)
+    }
+
+    /// Returns 32-bit `CRC-32C` checksum of the current window.
+    ///
+    /// See [`get`] for an example.
+    ///
+    /// [`get`]: InlineRollingDualCrc::get
+    #[inline(always)]
+    pub fn get32(&self) -> u32 {
+        !self.inverted_crc32
+    }
+
+    /// Returns 64-bit `CRC-64/XZ` checksum of the current window.
+    ///
+    /// See [`get`] for an example.
+    ///
+    /// [`get`]: InlineRollingDualCrc::get
+    #[inline(always)]
+    pub fn get64(&self) -> u64 {
+        !self.inverted_crc64
+    }
+
+    /// Begins computation of 32-bit `CRC-32C` and 64-bit `CRC-64/XZ`
+    /// rolling checksums.
+    ///
+    /// - Initializes local lookup tables (3 kiB total).
+    /// - Computes checksums of initial window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::InlineRollingDualCrc;
+    ///
+    /// let crc = InlineRollingDualCrc::new(*b"abc");
+    /// // checksum of "abc"
+    /// assert_eq!(crc.get32(), 0x364B3FB7);
+    /// ```
+    pub fn new(initial_window: [u8; N]) -> Self {
+        if N == 0 {
+            panic!("initial_window is empty");
+        }
+
+        let (crc32, crc64) = DualCrc::checksum(initial_window);
+
+        let mut table32 = [0u32; 256];
+        let mut table64 = [0u64; 256];
+        rolling_dual_crc::build_tables(N, &mut table32, &mut table64);
+
+        Self {
+            inverted_crc32: !crc32,
+            inverted_crc64: !crc64,
+
+            start_pos: 0,
+            data: initial_window,
+
+            table32,
+            table64,
+        }
+    }
+
+    /// Rolls window forward one byte.
+    ///
+    /// - Appends the given byte to the window.
+    /// - Removes first byte of the window.
+    /// - Recomputes checksums for the new window.
+    ///
+    /// This is a fast constant time `Θ(1)` operation
+    /// which doesn't depend on the size of the window.
+    ///
+    /// See [`InlineRollingDualCrc`] for an example.
+    #[inline(always)]
+    pub fn roll(&mut self, data: u8) {
+        let out = rolling_dual_crc::rotate_window(&mut self.start_pos, &mut self.data, data);
+        rolling_dual_crc::roll(
+            &mut self.inverted_crc32,
+            &mut self.inverted_crc64,
+            &self.table32,
+            &self.table64,
+            data,
+            out,
+        );
+    }
+
+    /// Rolls window forward.
+    ///
+    /// This is equivalent to calling [`roll`] for each byte of the given slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::InlineRollingDualCrc;
+    ///
+    /// let mut crc = InlineRollingDualCrc::new(*b"abc");
+    /// crc.roll_slice("de");
+    /// // checksum of "cde"
+    /// assert_eq!(crc.get32(), 0x364ADB60);
+    /// ```
+    ///
+    /// [`roll`]: InlineRollingDualCrc::roll
+    pub fn roll_slice<T: AsRef<[u8]>>(&mut self, data: T) {
+        for byte in data.as_ref() {
+            self.roll(*byte);
+        }
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn empty_initial_window() {
+        InlineRollingDualCrc::new([]);
+    }
+
+    // ============================================================
+    // new / roll / roll_slice
+
+    #[test]
+    fn roll_3_5() {
+        // Same checksums as in `RollingDualCrc` tests
+        let mut crc = InlineRollingDualCrc::new(*b"abc");
+        assert_eq!(crc.get(), (0x364B3FB7, 0x2CD8094A1A277627));
+        crc.roll(b'd');
+        assert_eq!(crc.get(), (0x1B0D0358, 0x0557EA6AA1219070));
+        crc.roll(b'e');
+        assert_eq!(crc.get(), (0x364ADB60, 0xB534844A0AD06B72));
+        crc.roll_slice("fgh");
+        assert_eq!(crc.get(), (0x861A094E, 0xB47462AF38541FB8));
+    }
+
+    #[test]
+    fn roll_1k() {
+        const WINDOW_SIZE: usize = 1024;
+
+        let input = include_bytes!("testdata/roll_1k-input");
+
+        let mut crc =
+            InlineRollingDualCrc::<WINDOW_SIZE>::new(input[..WINDOW_SIZE].try_into().unwrap());
+        for pos in 1..input.len() - WINDOW_SIZE {
+            crc.roll(input[pos + WINDOW_SIZE - 1]);
+            assert_eq!(crc.get(), DualCrc::checksum(&input[pos..pos + WINDOW_SIZE]));
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use std::{
+    io::{self, BufRead, Read, Seek, SeekFrom, Write},
+    ops::Range,
+};
+
+use crate::DualCrc;
+
+// ======================================================================
+// DualCrcReader - PUBLIC
+
+/// [`Read`] adapter which computes 32-bit `CRC-32C` and 64-bit `CRC-64/XZ`
+/// checksums of all data read through it.
+///
+/// - Implements [`BufRead`] if the inner reader does.
+///   Data is hashed when [`fill_buf`] returns it,
+///   so checksums may include bytes which aren't consumed yet.
+/// - Implements [`Seek`] if the inner reader does,
+///   but returns an error if seeking would go outside hashed bytes,
+///   i.e. past the furthest position read so far
+///   or before the position where reading started.
+///   Seeking backward is allowed, but then data which is read again
+///   is also included in checksums again.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::DualCrcReader;
+/// use std::io;
+///
+/// let mut reader = DualCrcReader::new("Hello, world!".as_bytes());
+/// io::copy(&mut reader, &mut io::sink()).unwrap();
+///
+/// assert_eq!(reader.bytes_processed(), 13);
+/// assert_eq!(reader.crc().get32(), 0xC8A106E5);
+/// ```
+///
+/// [`fill_buf`]: BufRead::fill_buf
+#[derive(Clone, Debug)]
+pub struct DualCrcReader<R> {
+    inner: R,
+    crc: DualCrc,
+    bytes_processed: u64,
+    // positions of `inner` which have been hashed, found on first seek
+    hashed: Option<Range<u64>>,
+    // bytes at start of buffer of `inner` which `fill_buf` has hashed
+    hashed_ahead: usize,
+}
+
+impl<R> DualCrcReader<R> {
+    /// Returns number of bytes read so far,
+    /// including bytes returned by [`fill_buf`] but not consumed yet.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
+    /// Returns checksums of the data read so far.
+    pub fn crc(&self) -> DualCrc {
+        self.crc
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Reading directly from the inner reader bypasses checksum computation.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwraps this [`DualCrcReader`], returning the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Creates a new [`DualCrcReader`] which reads from given reader.
+    ///
+    /// See [`DualCrcReader`] for an example.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            crc: DualCrc::new(),
+            bytes_processed: 0,
+            hashed: None,
+            hashed_ahead: 0,
+        }
+    }
+}
+
+// ======================================================================
+// DualCrcReader - IMPL Read / BufRead / Seek
+
+impl<R: Read> Read for DualCrcReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        // first bytes may have been hashed already by `fill_buf`
+        let skip = len.min(self.hashed_ahead);
+        self.hashed_ahead -= skip;
+        self.crc.update(&buf[skip..len]);
+        self.bytes_processed += (len - skip) as u64;
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for DualCrcReader<R> {
+    /// Returns contents of the inner buffer, hashing bytes not hashed yet.
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        if buf.len() > self.hashed_ahead {
+            self.crc.update(&buf[self.hashed_ahead..]);
+            self.bytes_processed += (buf.len() - self.hashed_ahead) as u64;
+            self.hashed_ahead = buf.len();
+        }
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.hashed_ahead = self.hashed_ahead.saturating_sub(amt);
+        self.inner.consume(amt);
+    }
+}
+
+impl<R: Seek> Seek for DualCrcReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        seek_within_hashed(
+            &mut self.inner,
+            &mut self.hashed,
+            self.bytes_processed,
+            &mut self.hashed_ahead,
+            pos,
+        )
+    }
+}
+
+// ======================================================================
+// DualCrcWriter - PUBLIC
+
+/// [`Write`] adapter which computes 32-bit `CRC-32C` and 64-bit `CRC-64/XZ`
+/// checksums of all data written through it.
+///
+/// - Implements [`Seek`] if the inner writer does,
+///   but returns an error if seeking would go outside hashed bytes,
+///   i.e. past the furthest position written so far
+///   or before the position where writing started.
+///   Seeking backward is allowed, but then data which is written again
+///   is also included in checksums again.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::DualCrcWriter;
+/// use std::io::Write;
+///
+/// let mut writer = DualCrcWriter::new(Vec::new());
+/// writer.write_all(b"Hello, world!").unwrap();
+///
+/// assert_eq!(writer.bytes_processed(), 13);
+/// assert_eq!(writer.crc().get32(), 0xC8A106E5);
+/// assert_eq!(writer.into_inner(), b"Hello, world!");
+/// ```
+#[derive(Clone, Debug)]
+pub struct DualCrcWriter<W> {
+    inner: W,
+    crc: DualCrc,
+    bytes_processed: u64,
+    // positions of `inner` which have been hashed, found on first seek
+    hashed: Option<Range<u64>>,
+}
+
+impl<W> DualCrcWriter<W> {
+    /// Returns number of bytes written so far.
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
+    /// Returns checksums of the data written so far.
+    pub fn crc(&self) -> DualCrc {
+        self.crc
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// Writing directly to the inner writer bypasses checksum computation.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwraps this [`DualCrcWriter`], returning the inner writer.
+    ///
+    /// The inner writer is not flushed.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Creates a new [`DualCrcWriter`] which writes to given writer.
+    ///
+    /// See [`DualCrcWriter`] for an example.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            crc: DualCrc::new(),
+            bytes_processed: 0,
+            hashed: None,
+        }
+    }
+}
+
+// ======================================================================
+// DualCrcWriter - IMPL Write / Seek
+
+impl<W: Write> Write for DualCrcWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.crc.update(&buf[..len]);
+        self.bytes_processed += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for DualCrcWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        seek_within_hashed(
+            &mut self.inner,
+            &mut self.hashed,
+            self.bytes_processed,
+            &mut 0,
+            pos,
+        )
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+/// Seeks to given position if it is within `hashed` positions of `inner`.
+///
+/// Before first seek position of `inner` has only moved forward
+/// by hashing `bytes_processed` bytes, except `hashed_ahead` bytes
+/// which are still in the buffer of `inner`, giving initial `hashed` range.
+/// After that the end of `hashed` is updated with current position,
+/// since data before it has been hashed when moving forward.
+///
+/// Seeking discards buffer of `inner`, so `hashed_ahead` is then cleared.
+///
+/// Target of [`SeekFrom::End`] can only be found by seeking,
+/// so for it position is restored on error.
+/// Otherwise target is checked before seeking.
+fn seek_within_hashed<S: Seek>(
+    inner: &mut S,
+    hashed: &mut Option<Range<u64>>,
+    bytes_processed: u64,
+    hashed_ahead: &mut usize,
+    pos: SeekFrom,
+) -> io::Result<u64> {
+    let current = inner.stream_position()?;
+    let hashed = hashed.get_or_insert_with(|| {
+        let bytes_behind = bytes_processed - *hashed_ahead as u64;
+        current.saturating_sub(bytes_behind)..current
+    });
+    hashed.end = hashed.end.max(current);
+
+    let target = match pos {
+        SeekFrom::Start(offset) => Some(offset),
+        SeekFrom::Current(offset) => current.checked_add_signed(offset),
+        SeekFrom::End(_) => {
+            *hashed_ahead = 0;
+            let target = inner.seek(pos)?;
+            if !is_hashed(hashed, target) {
+                inner.seek(SeekFrom::Start(current))?;
+                return Err(unhashed_error());
+            }
+            return Ok(target);
+        }
+    };
+
+    match target {
+        Some(target) if is_hashed(hashed, target) => {
+            *hashed_ahead = 0;
+            inner.seek(SeekFrom::Start(target))
+        }
+        Some(_) => Err(unhashed_error()),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )),
+    }
+}
+
+/// Returns `true` if reading or writing can continue from `position`
+/// without leaving unhashed bytes.
+fn is_hashed(hashed: &Range<u64>, position: u64) -> bool {
+    hashed.start <= position && position <= hashed.end
+}
+
+fn unhashed_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "seek would go outside hashed bytes",
+    )
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufReader, Cursor};
+
+    // ============================================================
+    // DualCrcReader
+
+    #[test]
+    fn reader_copy() {
+        let data = b"123456789".repeat(1000);
+        let mut reader = DualCrcReader::new(&data[..]);
+        let mut output = Vec::new();
+        io::copy(&mut reader, &mut output).unwrap();
+
+        assert_eq!(output, data);
+        assert_eq!(reader.bytes_processed(), 9000);
+        assert_eq!(reader.crc().get(), DualCrc::checksum(&data));
+    }
+
+    #[test]
+    fn reader_buf_read() {
+        let data = b"abc\ndef\nghi";
+        let mut reader = DualCrcReader::new(BufReader::with_capacity(2, &data[..]));
+        let lines: Vec<_> = (&mut reader).lines().map(Result::unwrap).collect();
+
+        assert_eq!(lines, ["abc", "def", "ghi"]);
+        assert_eq!(reader.bytes_processed(), 11);
+        assert_eq!(reader.crc().get(), DualCrc::checksum(data));
+    }
+
+    #[test]
+    fn reader_seek() {
+        let mut reader = DualCrcReader::new(Cursor::new(b"abcdef"));
+        let mut buf = [0u8; 3];
+        reader.read_exact(&mut buf).unwrap();
+
+        assert_eq!(reader.stream_position().unwrap(), 3);
+        assert!(reader.seek(SeekFrom::Current(1)).is_err());
+        assert!(reader.seek(SeekFrom::End(0)).is_err());
+        assert_eq!(reader.stream_position().unwrap(), 3);
+
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.crc().get(), DualCrc::checksum(b"abcdef"));
+    }
+
+    #[test]
+    fn reader_seek_forward_within_hashed() {
+        let mut reader = DualCrcReader::new(Cursor::new(b"abcdef"));
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf).unwrap();
+
+        assert_eq!(reader.seek(SeekFrom::Start(1)).unwrap(), 1);
+        assert_eq!(reader.seek(SeekFrom::Current(3)).unwrap(), 4);
+        assert!(reader.seek(SeekFrom::Current(1)).is_err());
+        assert!(reader.seek(SeekFrom::Current(-5)).is_err());
+        assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 4);
+        assert_eq!(reader.stream_position().unwrap(), 4);
+    }
+
+    #[test]
+    fn reader_seek_before_start() {
+        let mut inner = Cursor::new(b"abcdef");
+        inner.set_position(2);
+        let mut reader = DualCrcReader::new(inner);
+        let mut buf = [0u8; 2];
+        reader.read_exact(&mut buf).unwrap();
+
+        assert!(reader.seek(SeekFrom::Start(1)).is_err());
+        assert_eq!(reader.seek(SeekFrom::Start(2)).unwrap(), 2);
+        assert!(reader.seek(SeekFrom::Current(-1)).is_err());
+        assert_eq!(reader.stream_position().unwrap(), 2);
+    }
+
+    #[test]
+    fn reader_buf_read_partial_consume() {
+        let data = b"abcdefgh";
+        let mut reader = DualCrcReader::new(BufReader::with_capacity(4, &data[..]));
+
+        assert_eq!(reader.fill_buf().unwrap(), b"abcd");
+        reader.consume(1);
+        assert_eq!(reader.fill_buf().unwrap(), b"bcd");
+        reader.consume(1);
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"cdef");
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+
+        assert_eq!(rest, b"gh");
+        assert_eq!(reader.bytes_processed(), 8);
+        assert_eq!(reader.crc().get(), DualCrc::checksum(data));
+    }
+
+    #[test]
+    fn reader_seek_after_fill_buf() {
+        let data = b"abcdefgh";
+        let mut reader = DualCrcReader::new(BufReader::with_capacity(4, Cursor::new(data)));
+
+        reader.fill_buf().unwrap();
+        reader.consume(2);
+        assert!(reader.seek(SeekFrom::Start(3)).is_err());
+        assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+
+        assert_eq!(buf, data);
+        assert_eq!(reader.crc().get(), DualCrc::checksum(b"abcdabcdefgh"));
+    }
+
+    #[test]
+    fn reader_consume_without_io() {
+        struct FailingFillBuf(bool);
+
+        impl Read for FailingFillBuf {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                unreachable!()
+            }
+        }
+
+        impl BufRead for FailingFillBuf {
+            fn fill_buf(&mut self) -> io::Result<&[u8]> {
+                if self.0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "fill_buf called twice",
+                    ));
+                }
+                self.0 = true;
+                Ok(b"abc")
+            }
+
+            fn consume(&mut self, _amt: usize) {}
+        }
+
+        let mut reader = DualCrcReader::new(FailingFillBuf(false));
+        assert_eq!(reader.fill_buf().unwrap(), b"abc");
+        reader.consume(3);
+        assert_eq!(reader.crc().get(), DualCrc::checksum(b"abc"));
+    }
+
+    // ============================================================
+    // DualCrcWriter
+
+    #[test]
+    fn writer_copy() {
+        let data = b"123456789".repeat(1000);
+        let mut writer = DualCrcWriter::new(Vec::new());
+        io::copy(&mut &data[..], &mut writer).unwrap();
+
+        assert_eq!(writer.bytes_processed(), 9000);
+        assert_eq!(writer.crc().get(), DualCrc::checksum(&data));
+        assert_eq!(writer.into_inner(), data);
+    }
+
+    #[test]
+    fn writer_seek() {
+        let mut writer = DualCrcWriter::new(Cursor::new(Vec::new()));
+        writer.write_all(b"abc").unwrap();
+
+        assert!(writer.seek(SeekFrom::Start(4)).is_err());
+        assert_eq!(writer.seek(SeekFrom::Start(0)).unwrap(), 0);
+        assert_eq!(writer.crc().get(), DualCrc::checksum(b"abc"));
+    }
+}
@@ -1,10 +1,101 @@
 #![doc = include_str!(concat!(env!("OUT_DIR"), "/README-rustdocified.md"))]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
-pub use crate::{dual_crc::DualCrc, rolling_dual_crc::RollingDualCrc, zeros::Zeros};
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+pub use crate::{
+    crc::{Crc, CrcAlgorithm, CrcParams},
+    dual_crc::{BuildDualCrcHasher, DualCrc},
+    inline_rolling_dual_crc::InlineRollingDualCrc,
+    single_crc::{Crc32c, Crc64Xz},
+    zeros::Zeros,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::chunker::{Chunker, CHUNKER_WINDOW_SIZE};
+
+#[cfg(feature = "std")]
+pub use crate::chunker::ChunkReader;
+
+#[cfg(feature = "std")]
+pub use crate::delta::{apply_delta, ApplyDeltaError, Delta, DeltaOp, Signature};
+
+#[cfg(feature = "alloc")]
+pub use crate::find::{find, find_iter, FindIter, Finder};
+
+#[cfg(feature = "std")]
+pub use crate::io::{DualCrcReader, DualCrcWriter};
+
+#[cfg(feature = "std")]
+pub use crate::manifest::{
+    read_manifest, verify_manifest, CheckResult, CheckStatus, ManifestEntry, ManifestError,
+};
+
+#[cfg(feature = "alloc")]
+pub use crate::multi_rolling_dual_crc::MultiRollingDualCrc;
+
+#[cfg(feature = "std")]
+pub use crate::pattern_set::{PatternMatches, PatternSet};
+
+#[cfg(feature = "alloc")]
+pub use crate::rolling_crc::{RollingCrc, RollingCrc32IsoHdlc};
+
+#[cfg(feature = "alloc")]
+pub use crate::rolling_dual_crc::RollingDualCrc;
+
+#[cfg(feature = "std")]
+pub use crate::rolling_reader::RollingReader;
+
+#[cfg(feature = "alloc")]
+pub use crate::rolling_single_crc::{RollingCrc32c, RollingCrc64Xz};
+
+#[cfg(feature = "alloc")]
+pub use crate::rolling_tables::RollingTables;
+
+#[cfg(feature = "alloc")]
+pub use crate::windows::Windows;
+
+#[cfg(feature = "std")]
+pub use crate::windows::ReadWindows;
+
+pub mod catalogue;
+#[cfg(feature = "alloc")]
+mod chunker;
+mod crc;
+#[cfg(feature = "digest")]
+mod crc_digest;
+#[cfg(feature = "std")]
+mod delta;
 mod dual_crc;
+#[cfg(feature = "alloc")]
+mod find;
+mod inline_rolling_dual_crc;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod manifest;
+#[cfg(feature = "alloc")]
+mod multi_rolling_dual_crc;
+#[cfg(feature = "std")]
+mod pattern_set;
+#[cfg(feature = "alloc")]
+mod rolling_crc;
 mod rolling_dual_crc;
+#[cfg(feature = "std")]
+mod rolling_reader;
+#[cfg(feature = "alloc")]
+mod rolling_single_crc;
+#[cfg(feature = "alloc")]
+mod rolling_tables;
+mod single_crc;
 mod tables;
+#[cfg(all(test, feature = "alloc"))]
+mod test_util;
+#[cfg(feature = "alloc")]
+mod window_buffer;
+#[cfg(feature = "alloc")]
+mod windows;
 mod zeros;
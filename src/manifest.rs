@@ -0,0 +1,480 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufRead, Read},
+    path::PathBuf,
+};
+
+use crate::{DualCrc, DualCrcReader};
+
+// ======================================================================
+// CONST - PRIVATE
+
+const TAG_32: &str = "CRC32C";
+const TAG_64: &str = "CRC64XZ";
+
+// ======================================================================
+// ManifestEntry - PUBLIC
+
+/// One line of a checksum manifest.
+///
+/// Two line formats are supported, both with lowercase or uppercase hexadecimal checksums:
+///
+/// - `crc32c crc64xz  path`, as printed by `rdcrc`,
+///   also `crc32c  path` or `crc64xz  path` with only one checksum
+///   (recognized by its length), and `*` instead of second space is allowed
+/// - BSD-tagged `CRC32C (path) = crc32c` or `CRC64XZ (path) = crc64xz`
+///
+/// [`Display`] formats entry in the first format, or in the BSD-tagged format
+/// if it has only one checksum.
+/// Entry without any checksums is formatted as `  path`,
+/// which is not a valid manifest line and is rejected by [`parse`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{DualCrc, ManifestEntry};
+///
+/// let entry = ManifestEntry::parse("e3069283 995dc9bbdf1939fa  check.txt").unwrap();
+/// assert_eq!(entry.path.to_str(), Some("check.txt"));
+/// assert!(entry.matches(DualCrc::checksum("123456789")));
+///
+/// let entry = ManifestEntry::parse("CRC32C (check.txt) = E3069283").unwrap();
+/// assert_eq!(entry.crc32, Some(0xE3069283));
+/// assert_eq!(entry.crc64, None);
+/// ```
+///
+/// [`Display`]: fmt::Display
+/// [`parse`]: ManifestEntry::parse
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Expected `CRC-32C` checksum, if any.
+    pub crc32: Option<u32>,
+    /// Expected `CRC-64/XZ` checksum, if any.
+    pub crc64: Option<u64>,
+}
+
+impl ManifestEntry {
+    /// Returns `true` if given checksums match all checksums of this entry.
+    pub fn matches(&self, crc: (u32, u64)) -> bool {
+        self.crc32.map_or(true, |crc32| crc32 == crc.0)
+            && self.crc64.map_or(true, |crc64| crc64 == crc.1)
+    }
+
+    /// Creates entry with both checksums.
+    pub fn new<P: Into<PathBuf>>(path: P, crc: &DualCrc) -> Self {
+        Self {
+            path: path.into(),
+            crc32: Some(crc.get32()),
+            crc64: Some(crc.get64()),
+        }
+    }
+
+    /// Parses one manifest line, without line terminator.
+    ///
+    /// Returns `None` if the line is improperly formatted.
+    ///
+    /// See [`ManifestEntry`] for an example.
+    pub fn parse(line: &str) -> Option<Self> {
+        Self::parse_tagged(line).or_else(|| Self::parse_untagged(line))
+    }
+
+    /// Computes checksums of the file and compares them to this entry.
+    pub fn verify(&self) -> CheckStatus {
+        match File::open(&self.path).and_then(checksum_reader) {
+            Ok(crc) if self.matches(crc.get()) => CheckStatus::Ok,
+            Ok(_) => CheckStatus::Failed,
+            Err(_) => CheckStatus::Missing,
+        }
+    }
+}
+
+// ======================================================================
+// ManifestEntry - PRIVATE
+
+impl ManifestEntry {
+    fn parse_tagged(line: &str) -> Option<Self> {
+        let (tag, rest) = line.split_once(" (")?;
+        let (path, hex) = rest.rsplit_once(") = ")?;
+
+        let (crc32, crc64) = match tag {
+            TAG_32 if hex.len() == 8 => (Some(u32::from_str_radix(hex, 16).ok()?), None),
+            TAG_64 if hex.len() == 16 => (None, Some(u64::from_str_radix(hex, 16).ok()?)),
+            _ => return None,
+        };
+
+        if path.is_empty() || !is_hex(hex) {
+            return None;
+        }
+
+        Some(Self {
+            path: path.into(),
+            crc32,
+            crc64,
+        })
+    }
+
+    fn parse_untagged(line: &str) -> Option<Self> {
+        let (checksums, path) = line.split_once("  ").or_else(|| line.split_once(" *"))?;
+
+        if path.is_empty() || !checksums.split(' ').all(is_hex) {
+            return None;
+        }
+
+        let (crc32, crc64) = match checksums.split_once(' ') {
+            Some((crc32, crc64)) if crc32.len() == 8 && crc64.len() == 16 => (
+                Some(u32::from_str_radix(crc32, 16).ok()?),
+                Some(u64::from_str_radix(crc64, 16).ok()?),
+            ),
+            None if checksums.len() == 8 => (Some(u32::from_str_radix(checksums, 16).ok()?), None),
+            None if checksums.len() == 16 => (None, Some(u64::from_str_radix(checksums, 16).ok()?)),
+            _ => return None,
+        };
+
+        Some(Self {
+            path: path.into(),
+            crc32,
+            crc64,
+        })
+    }
+}
+
+// ======================================================================
+// ManifestEntry - IMPL Display
+
+impl fmt::Display for ManifestEntry {
+    /// Formats entry as one manifest line, without line terminator.
+    ///
+    /// See [`ManifestEntry`] for the format.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = self.path.display();
+        match (self.crc32, self.crc64) {
+            (Some(crc32), Some(crc64)) => write!(f, "{:08x} {:016x}  {}", crc32, crc64, path),
+            (Some(crc32), None) => write!(f, "{} ({}) = {:08x}", TAG_32, path, crc32),
+            (None, Some(crc64)) => write!(f, "{} ({}) = {:016x}", TAG_64, path, crc64),
+            (None, None) => write!(f, "  {}", path),
+        }
+    }
+}
+
+// ======================================================================
+// CheckStatus / CheckResult - PUBLIC
+
+/// Result of verifying one [`ManifestEntry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Checksums match.
+    Ok,
+    /// Checksums don't match.
+    Failed,
+    /// File could not be opened or read.
+    Missing,
+}
+
+/// Path and [`CheckStatus`] of one verified [`ManifestEntry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Result of the verification.
+    pub status: CheckStatus,
+}
+
+// ======================================================================
+// ManifestError - PUBLIC
+
+/// Error returned by [`read_manifest`] and [`verify_manifest`].
+#[derive(Debug)]
+pub enum ManifestError {
+    /// Reading the manifest failed.
+    Io(io::Error),
+    /// Manifest has an improperly formatted line.
+    Parse {
+        /// Line number, starting from 1.
+        line: usize,
+    },
+    /// Some files didn't match the manifest.
+    Mismatch {
+        /// Results of all entries.
+        results: Vec<CheckResult>,
+        /// Number of files with [`CheckStatus::Failed`].
+        failed: usize,
+        /// Number of files with [`CheckStatus::Missing`].
+        missing: usize,
+    },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ManifestError::Io(error) => write!(f, "reading manifest failed: {}", error),
+            ManifestError::Parse { line } => {
+                write!(f, "line {} is improperly formatted", line)
+            }
+            ManifestError::Mismatch {
+                failed, missing, ..
+            } => write!(
+                f,
+                "{} computed checksums did NOT match, {} listed files could not be read",
+                failed, missing
+            ),
+        }
+    }
+}
+
+impl Error for ManifestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ManifestError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ManifestError {
+    fn from(error: io::Error) -> Self {
+        ManifestError::Io(error)
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Reads all entries of a manifest. Empty lines are skipped.
+///
+/// # Errors
+///
+/// Returns [`ManifestError::Io`] if reading fails
+/// and [`ManifestError::Parse`] on first improperly formatted line.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::read_manifest;
+///
+/// let manifest = "e3069283 995dc9bbdf1939fa  a.txt\n\nCRC64XZ (b.txt) = 2cd8094a1a277627\n";
+/// let entries = read_manifest(manifest.as_bytes()).unwrap();
+/// assert_eq!(entries.len(), 2);
+/// ```
+pub fn read_manifest<R: BufRead>(reader: R) -> Result<Vec<ManifestEntry>, ManifestError> {
+    let mut entries = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if line.is_empty() {
+            continue;
+        }
+        entries.push(ManifestEntry::parse(line).ok_or(ManifestError::Parse { line: n + 1 })?);
+    }
+    Ok(entries)
+}
+
+/// Verifies all files of given manifest entries.
+///
+/// Relative paths are relative to the current directory.
+///
+/// # Errors
+///
+/// Returns [`ManifestError::Mismatch`], which includes results of all entries,
+/// unless status of all entries is [`CheckStatus::Ok`].
+pub fn verify_manifest(entries: &[ManifestEntry]) -> Result<Vec<CheckResult>, ManifestError> {
+    let results: Vec<_> = entries
+        .iter()
+        .map(|entry| CheckResult {
+            path: entry.path.clone(),
+            status: entry.verify(),
+        })
+        .collect();
+
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    let failed = count(CheckStatus::Failed);
+    let missing = count(CheckStatus::Missing);
+
+    if failed == 0 && missing == 0 {
+        Ok(results)
+    } else {
+        Err(ManifestError::Mismatch {
+            results,
+            failed,
+            missing,
+        })
+    }
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+/// Computes checksums of all data from given reader.
+fn checksum_reader<R: Read>(reader: R) -> io::Result<DualCrc> {
+    let mut reader = DualCrcReader::new(reader);
+    io::copy(&mut reader, &mut io::sink())?;
+    Ok(reader.crc())
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{env, fs};
+
+    // ============================================================
+    // ManifestEntry
+
+    #[test]
+    fn parse_untagged() {
+        assert_eq!(
+            ManifestEntry::parse("e3069283 995DC9BBDF1939FA  dir/a file.txt"),
+            Some(ManifestEntry {
+                path: "dir/a file.txt".into(),
+                crc32: Some(0xE3069283),
+                crc64: Some(0x995DC9BBDF1939FA),
+            })
+        );
+        assert_eq!(
+            ManifestEntry::parse("e3069283 *a"),
+            Some(ManifestEntry {
+                path: "a".into(),
+                crc32: Some(0xE3069283),
+                crc64: None,
+            })
+        );
+        assert_eq!(
+            ManifestEntry::parse("995dc9bbdf1939fa  a"),
+            Some(ManifestEntry {
+                path: "a".into(),
+                crc32: None,
+                crc64: Some(0x995DC9BBDF1939FA),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_tagged() {
+        assert_eq!(
+            ManifestEntry::parse("CRC64XZ (a (1).txt) = 995dc9bbdf1939fa"),
+            Some(ManifestEntry {
+                path: "a (1).txt".into(),
+                crc32: None,
+                crc64: Some(0x995DC9BBDF1939FA),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for line in [
+            "",
+            "e3069283",
+            "e3069283  ",
+            "e306928  a",
+            "e3069283 995dc9bbdf1939f  a",
+            "+3069283  a",
+            "e306928x 995dc9bbdf1939fa  a",
+            "CRC32C (a) = 995dc9bbdf1939fa",
+            "CRC32C () = e3069283",
+            "SHA256 (a) = e3069283",
+        ] {
+            assert_eq!(ManifestEntry::parse(line), None, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        let crc = DualCrc::checksum("123456789");
+        for entry in [
+            ManifestEntry::new("a b", &DualCrc::new()),
+            ManifestEntry {
+                path: "c".into(),
+                crc32: Some(crc.0),
+                crc64: None,
+            },
+            ManifestEntry {
+                path: "d".into(),
+                crc32: None,
+                crc64: Some(crc.1),
+            },
+        ] {
+            assert_eq!(ManifestEntry::parse(&entry.to_string()), Some(entry));
+        }
+        assert_eq!(
+            ManifestEntry::new("a", &DualCrc::new()).to_string(),
+            "00000000 0000000000000000  a"
+        );
+    }
+
+    #[test]
+    fn display_without_checksums_is_not_parseable() {
+        let entry = ManifestEntry {
+            path: "a".into(),
+            crc32: None,
+            crc64: None,
+        };
+        assert_eq!(entry.to_string(), "  a");
+        assert_eq!(ManifestEntry::parse(&entry.to_string()), None);
+    }
+
+    // ============================================================
+    // read_manifest / verify_manifest
+
+    #[test]
+    fn read_manifest_parse_error() {
+        let manifest = "e3069283  a\r\n\ninvalid\n";
+        assert!(matches!(
+            read_manifest(manifest.as_bytes()),
+            Err(ManifestError::Parse { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn verify_manifest_files() {
+        let dir = env::temp_dir().join(format!("rolling-dual-crc-manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("check.txt");
+        fs::write(&path, "123456789").unwrap();
+
+        // "check" values from "Catalogue of parametrised CRC algorithms"
+        let ok = ManifestEntry {
+            path: path.clone(),
+            crc32: Some(0xE3069283),
+            crc64: Some(0x995DC9BBDF1939FA),
+        };
+        let failed = ManifestEntry::new(&path, &DualCrc::new());
+        let missing = ManifestEntry::new(dir.join("missing.txt"), &DualCrc::new());
+
+        assert_eq!(
+            verify_manifest(std::slice::from_ref(&ok)).unwrap(),
+            [CheckResult {
+                path: path.clone(),
+                status: CheckStatus::Ok
+            }]
+        );
+
+        match verify_manifest(&[ok, failed, missing]) {
+            Err(ManifestError::Mismatch {
+                results,
+                failed,
+                missing,
+            }) => {
+                let statuses: Vec<_> = results.iter().map(|r| r.status).collect();
+                assert_eq!(
+                    statuses,
+                    [CheckStatus::Ok, CheckStatus::Failed, CheckStatus::Missing]
+                );
+                assert_eq!((failed, missing), (1, 1));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,333 @@
+use alloc::vec::Vec;
+
+use crate::{rolling_dual_crc, window_buffer::WindowBuffer, DualCrc, RollingTables};
+
+// ======================================================================
+// MultiRollingDualCrc - PUBLIC
+
+/// Computes 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums
+/// in several rolling windows of different sizes which move
+/// through the input data together.
+///
+/// All windows end at the same byte, i.e. each window is a suffix
+/// of the largest window. This is like using one [`RollingDualCrc`]
+/// per window size, except that
+/// - contents of the largest window are stored only once,
+///   from which bytes leaving each smaller window are also read.
+/// - windows of same size share their lookup tables.
+///
+/// Window with index `i` has size `window_sizes[i]` as given to [`new`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{MultiRollingDualCrc, RollingDualCrc};
+///
+/// let mut crc = MultiRollingDualCrc::new(&[2, 3], "abc");
+///
+/// // checksums of "bc" and "abc"
+/// assert_eq!(crc.get(0), RollingDualCrc::new("bc").get());
+/// assert_eq!(crc.get32(1), 0x364B3FB7);
+///
+/// crc.roll(b'd');
+/// // checksums of "cd" and "bcd"
+/// assert_eq!(crc.get(0), RollingDualCrc::new("cd").get());
+/// assert_eq!(crc.get32(1), 0x1B0D0358);
+/// ```
+///
+/// [`new`]: MultiRollingDualCrc::new
+/// [`RollingDualCrc`]: crate::RollingDualCrc
+#[derive(Clone, Debug)]
+pub struct MultiRollingDualCrc {
+    windows: Vec<Window>,
+    window_sizes: Vec<usize>,
+
+    /// Contents of the largest window.
+    data: WindowBuffer,
+}
+
+impl MultiRollingDualCrc {
+    /// Returns 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums
+    /// of the current window with given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds of `window_sizes`.
+    ///
+    /// See [`MultiRollingDualCrc`] for an example.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> (u32, u64) {
+        let window = &self.windows[index];
+        (!window.inverted_crc32, !window.inverted_crc64)
+    }
+
+    /// Returns 32-bit `CRC-32C` checksum of the current window with given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds of `window_sizes`.
+    ///
+    /// See [`MultiRollingDualCrc`] for an example.
+    #[inline(always)]
+    pub fn get32(&self, index: usize) -> u32 {
+        !self.windows[index].inverted_crc32
+    }
+
+    /// Returns 64-bit `CRC-64/XZ` checksum of the current window with given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds of `window_sizes`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::MultiRollingDualCrc;
+    ///
+    /// let crc = MultiRollingDualCrc::new(&[3], "abc");
+    /// // checksum of "abc"
+    /// assert_eq!(crc.get64(0), 0x2CD8094A1A277627);
+    /// ```
+    #[inline(always)]
+    pub fn get64(&self, index: usize) -> u64 {
+        !self.windows[index].inverted_crc64
+    }
+
+    /// Begins computation of rolling checksums in windows of given sizes.
+    ///
+    /// - `initial_window` is the initial contents of the largest window,
+    ///   and initial contents of each smaller window is its suffix.
+    /// - Computes checksums of the initial windows.
+    /// - Allocates and initializes local lookup tables (3 kiB total)
+    ///   for each distinct window size.
+    ///
+    /// Same window size can be given several times,
+    /// in which case those windows share their lookup tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if
+    /// - `window_sizes` is empty or contains zero.
+    /// - size of `initial_window` differs from largest of `window_sizes`.
+    ///
+    /// See [`MultiRollingDualCrc`] for an example.
+    pub fn new<T: AsRef<[u8]>>(window_sizes: &[usize], initial_window: T) -> Self {
+        let initial_window = initial_window.as_ref();
+
+        let max_size = match window_sizes.iter().max() {
+            Some(max_size) => *max_size,
+            None => panic!("window_sizes is empty"),
+        };
+        if window_sizes.contains(&0) {
+            panic!("window_sizes contains zero");
+        }
+        if initial_window.len() != max_size {
+            panic!(
+                "initial_window size {} differs from largest window_size {}",
+                initial_window.len(),
+                max_size
+            );
+        }
+
+        let mut windows: Vec<Window> = Vec::with_capacity(window_sizes.len());
+        for (index, window_size) in window_sizes.iter().enumerate() {
+            let tables = match window_sizes[..index].iter().position(|x| x == window_size) {
+                Some(earlier) => windows[earlier].tables.clone(),
+                None => RollingTables::new(*window_size),
+            };
+            let start = max_size - window_size;
+            let (crc32, crc64) = DualCrc::checksum(&initial_window[start..]);
+
+            windows.push(Window {
+                inverted_crc32: !crc32,
+                inverted_crc64: !crc64,
+                start,
+                tables,
+            });
+        }
+
+        Self {
+            windows,
+            window_sizes: window_sizes.to_vec(),
+
+            data: WindowBuffer::new(initial_window),
+        }
+    }
+
+    /// Rolls all windows forward one byte.
+    ///
+    /// - Appends the given byte to each window.
+    /// - Removes first byte of each window.
+    /// - Recomputes checksums for the new windows.
+    ///
+    /// Complexity: `Θ(k)` time, where `k` is number of windows
+    ///
+    /// See [`MultiRollingDualCrc`] for an example.
+    #[inline(always)]
+    pub fn roll(&mut self, data: u8) {
+        for window in &mut self.windows {
+            rolling_dual_crc::roll(
+                &mut window.inverted_crc32,
+                &mut window.inverted_crc64,
+                window.tables.table32(),
+                window.tables.table64(),
+                data,
+                self.data.get(window.start),
+            );
+        }
+
+        self.data.roll(data);
+    }
+
+    /// Rolls all windows forward multiple bytes.
+    ///
+    /// This is same as calling [`roll`] for each byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::{MultiRollingDualCrc, RollingDualCrc};
+    ///
+    /// let mut crc = MultiRollingDualCrc::new(&[1, 3], "abc");
+    /// crc.roll_slice("de");
+    /// // checksums of "e" and "cde"
+    /// assert_eq!(crc.get(0), RollingDualCrc::new("e").get());
+    /// assert_eq!(crc.get32(1), 0x364ADB60);
+    /// ```
+    ///
+    /// [`roll`]: MultiRollingDualCrc::roll
+    pub fn roll_slice<T: AsRef<[u8]>>(&mut self, data: T) {
+        for byte in data.as_ref() {
+            self.roll(*byte);
+        }
+    }
+
+    /// Returns contents of the current largest window as two slices, in order.
+    ///
+    /// Contents of window with index `i` are
+    /// the last `window_sizes()[i]` bytes of this.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::MultiRollingDualCrc;
+    ///
+    /// let mut crc = MultiRollingDualCrc::new(&[2, 3], "abc");
+    /// crc.roll(b'd');
+    ///
+    /// let (front, back) = crc.window();
+    /// assert_eq!([front, back].concat(), b"bcd");
+    /// ```
+    pub fn window(&self) -> (&[u8], &[u8]) {
+        self.data.as_slices()
+    }
+
+    /// Returns sizes of the windows, in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::MultiRollingDualCrc;
+    ///
+    /// let crc = MultiRollingDualCrc::new(&[48, 4096], vec![0; 4096]);
+    /// assert_eq!(crc.window_sizes(), [48, 4096]);
+    /// ```
+    pub fn window_sizes(&self) -> &[usize] {
+        &self.window_sizes
+    }
+}
+
+// ======================================================================
+// Window - PRIVATE
+
+/// Rolling checksums of one window.
+#[derive(Clone, Debug)]
+struct Window {
+    inverted_crc32: u32,
+    inverted_crc64: u64,
+    /// Index of the first byte of this window within the largest window.
+    start: usize,
+    tables: RollingTables,
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::random_data, RollingDualCrc};
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn new_empty_window_sizes() {
+        MultiRollingDualCrc::new(&[], "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_zero_window_size() {
+        MultiRollingDualCrc::new(&[0, 3], "abc");
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_wrong_initial_window_size() {
+        MultiRollingDualCrc::new(&[2, 3], "ab");
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_out_of_bounds() {
+        MultiRollingDualCrc::new(&[2, 3], "abc").get(2);
+    }
+
+    // ============================================================
+    // roll
+
+    #[test]
+    fn roll_same_as_separate() {
+        let data = random_data(10_000);
+        let window_sizes = [48, 4096, 1, 48, 100];
+
+        let mut crc = MultiRollingDualCrc::new(&window_sizes, &data[..4096]);
+        let mut expected: Vec<RollingDualCrc> = window_sizes
+            .iter()
+            .map(|size| RollingDualCrc::new(&data[4096 - size..4096]))
+            .collect();
+
+        for byte in &data[4096..] {
+            for (index, separate) in expected.iter().enumerate() {
+                assert_eq!(crc.get(index), separate.get());
+            }
+            crc.roll(*byte);
+            for separate in &mut expected {
+                separate.roll(*byte);
+            }
+        }
+
+        for (index, window_size) in window_sizes.iter().enumerate() {
+            assert_eq!(
+                crc.get(index),
+                DualCrc::checksum(&data[data.len() - window_size..])
+            );
+        }
+        let (front, back) = crc.window();
+        assert_eq!([front, back].concat(), &data[data.len() - 4096..]);
+    }
+
+    #[test]
+    fn new_shares_tables() {
+        let crc = MultiRollingDualCrc::new(&[2, 3, 2], "abc");
+        assert_eq!(crc.window_sizes(), [2, 3, 2]);
+        assert_eq!(crc.get(0), crc.get(2));
+        assert!(core::ptr::eq(
+            crc.windows[0].tables.table32(),
+            crc.windows[2].tables.table32()
+        ));
+    }
+}
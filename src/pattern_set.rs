@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use alloc::vec::Vec;
+
+use crate::{DualCrc, RollingDualCrc, RollingTables};
+
+// ======================================================================
+// PatternSet - PUBLIC
+
+/// Set of patterns for searching all of them at once using [`RollingDualCrc`].
+///
+/// Patterns are indexed by their `(crc32, crc64)` checksums,
+/// with one index and one rolling window per distinct pattern length.
+/// [`scan`] rolls these windows through the data and looks up
+/// checksums of each window from the index of its length.
+///
+/// Scanning takes `Θ(n * k)` time, where `n` is size of the data and
+/// `k` is number of distinct pattern lengths, regardless of number of patterns.
+///
+/// Only checksums of the patterns are stored, so a match means that
+/// both checksums of the data equal those of the pattern.
+/// For random data a false match is very unlikely, but note that CRC
+/// is not a cryptographic hash, so such data can be crafted deliberately.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::PatternSet;
+///
+/// let mut patterns = PatternSet::new();
+/// let fox = patterns.insert("fox");
+/// let dog = patterns.insert("dog");
+/// let lazy = patterns.insert("lazy");
+///
+/// let matches: Vec<(usize, usize)> = patterns
+///     .scan(b"The quick brown fox jumps over the lazy dog.")
+///     .collect();
+/// assert_eq!(matches, [(16, fox), (35, lazy), (40, dog)]);
+/// ```
+///
+/// [`scan`]: PatternSet::scan
+#[derive(Clone, Debug, Default)]
+pub struct PatternSet {
+    /// Sorted by pattern length.
+    groups: Vec<Group>,
+    /// Pattern length of each pattern id.
+    pattern_lens: Vec<usize>,
+}
+
+impl PatternSet {
+    /// Inserts given pattern to this set and returns its pattern id.
+    ///
+    /// Pattern ids are `0, 1, 2, ...` in insertion order.
+    /// Same pattern can be inserted several times, in which case
+    /// each match of it is reported with each of its pattern ids.
+    ///
+    /// Complexity: `Θ(m)` time, where `m` is size of the pattern,
+    /// plus building local lookup tables (3 kiB total)
+    /// for the first pattern of each length
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is empty.
+    ///
+    /// See [`PatternSet`] for an example.
+    pub fn insert<T: AsRef<[u8]>>(&mut self, pattern: T) -> usize {
+        let pattern = pattern.as_ref();
+
+        if pattern.is_empty() {
+            panic!("pattern is empty");
+        }
+
+        let pos = match self
+            .groups
+            .binary_search_by_key(&pattern.len(), |group| group.tables.window_size())
+        {
+            Ok(pos) => pos,
+            Err(pos) => {
+                self.groups.insert(
+                    pos,
+                    Group {
+                        tables: RollingTables::new(pattern.len()),
+                        index: HashMap::new(),
+                    },
+                );
+                pos
+            }
+        };
+
+        let pattern_id = self.pattern_lens.len();
+        self.groups[pos]
+            .index
+            .entry(DualCrc::checksum(pattern))
+            .or_default()
+            .push(pattern_id);
+        self.pattern_lens.push(pattern.len());
+        pattern_id
+    }
+
+    /// Returns `true` if this set has no patterns.
+    pub fn is_empty(&self) -> bool {
+        self.pattern_lens.is_empty()
+    }
+
+    /// Returns number of patterns in this set.
+    pub fn len(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    /// Creates a new empty [`PatternSet`].
+    ///
+    /// See [`PatternSet`] for an example.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns length of the pattern with given pattern id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no pattern with given pattern id.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::PatternSet;
+    ///
+    /// let mut patterns = PatternSet::new();
+    /// let id = patterns.insert("lazy");
+    /// assert_eq!(patterns.pattern_len(id), 4);
+    /// ```
+    pub fn pattern_len(&self, pattern_id: usize) -> usize {
+        self.pattern_lens[pattern_id]
+    }
+
+    /// Returns iterator over all matches of all patterns in given data.
+    ///
+    /// Iterator yields `(offset, pattern_id)` for each match,
+    /// ordered by offset, then by pattern length, then by pattern id.
+    /// Matches may overlap.
+    ///
+    /// See [`PatternSet`] for an example.
+    pub fn scan<'a>(&'a self, data: &'a [u8]) -> PatternMatches<'a> {
+        let crcs = self
+            .groups
+            .iter()
+            .map(|group| {
+                data.get(..group.tables.window_size())
+                    .map(|window| RollingDualCrc::with_tables(group.tables.clone(), window))
+            })
+            .collect();
+
+        PatternMatches {
+            set: self,
+            data,
+
+            offset: 0,
+            group: 0,
+            crcs,
+            pending: &[],
+        }
+    }
+}
+
+// ======================================================================
+// PatternMatches - PUBLIC
+
+/// Iterator over matches of patterns of [`PatternSet`] in data.
+///
+/// This is created by [`PatternSet::scan`].
+#[derive(Clone, Debug)]
+pub struct PatternMatches<'a> {
+    set: &'a PatternSet,
+    data: &'a [u8],
+
+    /// Offset of current windows.
+    offset: usize,
+    /// Index of next group to check at current offset.
+    group: usize,
+    /// Rolling window for each group, `None` when past end of data.
+    crcs: Vec<Option<RollingDualCrc>>,
+    /// Matched pattern ids not yet returned.
+    pending: &'a [usize],
+}
+
+// ======================================================================
+// PatternMatches - IMPL Iterator
+
+impl<'a> Iterator for PatternMatches<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((pattern_id, rest)) = self.pending.split_first() {
+                self.pending = rest;
+                return Some((self.offset, *pattern_id));
+            }
+
+            if self.group < self.crcs.len() {
+                if let Some(crc) = &self.crcs[self.group] {
+                    if let Some(pattern_ids) = self.set.groups[self.group].index.get(&crc.get()) {
+                        self.pending = pattern_ids;
+                    }
+                }
+                self.group += 1;
+                continue;
+            }
+
+            self.offset += 1;
+            self.group = 0;
+
+            let mut done = true;
+            for slot in self.crcs.iter_mut() {
+                if let Some(crc) = slot {
+                    match self.data.get(self.offset + crc.tables().window_size() - 1) {
+                        Some(byte) => {
+                            crc.roll(*byte);
+                            done = false;
+                        }
+                        None => *slot = None,
+                    }
+                }
+            }
+
+            if done {
+                return None;
+            }
+        }
+    }
+}
+
+// ======================================================================
+// Group - PRIVATE
+
+/// Patterns of one length.
+#[derive(Clone, Debug)]
+struct Group {
+    tables: RollingTables,
+    index: HashMap<(u32, u64), Vec<usize>>,
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::random_data;
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn insert_empty_pattern() {
+        PatternSet::new().insert([]);
+    }
+
+    // ============================================================
+    // insert / len / pattern_len
+
+    #[test]
+    fn insert_len() {
+        let mut patterns = PatternSet::new();
+        assert!(patterns.is_empty());
+        assert_eq!(patterns.insert("abc"), 0);
+        assert_eq!(patterns.insert("a"), 1);
+        assert_eq!(patterns.insert("abc"), 2);
+        assert_eq!(patterns.len(), 3);
+        assert_eq!(patterns.pattern_len(1), 1);
+        assert_eq!(patterns.groups.len(), 2);
+    }
+
+    // ============================================================
+    // scan
+
+    #[test]
+    fn scan_same_as_naive() {
+        let data = random_data(10_000);
+        // (offset, len) of patterns in data, last one is duplicate
+        let specs = [
+            (100, 16),
+            (5000, 16),
+            (200, 1),
+            (9990, 10),
+            (100, 32),
+            (5000, 16),
+        ];
+
+        let mut patterns = PatternSet::new();
+        for (offset, len) in specs {
+            patterns.insert(&data[offset..offset + len]);
+        }
+
+        let mut by_len: Vec<(usize, usize)> = (0..specs.len())
+            .map(|pattern_id| (specs[pattern_id].1, pattern_id))
+            .collect();
+        by_len.sort();
+
+        let mut expected = Vec::new();
+        for offset in 0..data.len() {
+            for (len, pattern_id) in &by_len {
+                let (pattern_offset, _) = specs[*pattern_id];
+                if data[offset..].starts_with(&data[pattern_offset..pattern_offset + len]) {
+                    expected.push((offset, *pattern_id));
+                }
+            }
+        }
+
+        let matches: Vec<(usize, usize)> = patterns.scan(&data).collect();
+        assert_eq!(matches, expected);
+        assert!(matches.contains(&(5000, 5)));
+        assert!(matches.contains(&(9990, 3)));
+    }
+
+    #[test]
+    fn scan_short_data() {
+        let mut patterns = PatternSet::new();
+        patterns.insert("abcd");
+        patterns.insert("ab");
+
+        assert_eq!(patterns.scan(b"").count(), 0);
+        assert_eq!(patterns.scan(b"xab").collect::<Vec<_>>(), [(1, 1)]);
+        assert_eq!(patterns.scan(b"abcd").collect::<Vec<_>>(), [(0, 1), (0, 0)]);
+        assert_eq!(PatternSet::new().scan(b"abcd").count(), 0);
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::marker::PhantomData;
+
+use crate::{
+    catalogue::Crc32IsoHdlc,
+    crc::{self, Crc},
+    rolling_dual_crc, CrcAlgorithm,
+};
+
+// ======================================================================
+// TYPES - PUBLIC
+
+/// Computes 32-bit `CRC-32/ISO-HDLC` checksum, i.e. checksum of zlib,
+/// in a rolling window that moves through the input data.
+///
+/// See [`RollingCrc`] for an example.
+pub type RollingCrc32IsoHdlc = RollingCrc<Crc32IsoHdlc>;
+
+// ======================================================================
+// RollingCrc - PUBLIC
+
+/// Computes checksum of any [`CrcAlgorithm`]
+/// in a rolling window that moves through the input data.
+///
+/// This works like [`RollingDualCrc`], but for a single algorithm.
+/// Local lookup table is 2 kiB regardless of `width` of the algorithm.
+///
+/// # Examples
+///
+/// Compute `CRC-32/ISO-HDLC` checksums of 3-byte windows of `"abcde"`,
+/// i.e. `"abc"`, `"bcd"` and `"cde"`.
+///
+/// ```rust
+/// use rolling_dual_crc::RollingCrc32IsoHdlc;
+///
+/// let mut crc = RollingCrc32IsoHdlc::new("abc");
+///
+/// // checksum of "abc"
+/// assert_eq!(crc.get(), 0x352441C2);
+///
+/// crc.roll(b'd');
+/// // checksum of "bcd"
+/// assert_eq!(crc.get(), 0xB01D5B79);
+///
+/// crc.roll(b'e');
+/// // checksum of "cde"
+/// assert_eq!(crc.get(), 0x8999971F);
+/// ```
+///
+/// [`RollingDualCrc`]: crate::RollingDualCrc
+#[derive(Clone, Debug)]
+pub struct RollingCrc<A> {
+    register: u64,
+
+    start_pos: usize,
+    data: Vec<u8>,
+
+    table: Box<[u64; 256]>,
+
+    algorithm: PhantomData<A>,
+}
+
+impl<A: CrcAlgorithm> RollingCrc<A> {
+    /// Returns checksum of the current window.
+    ///
+    /// See [`RollingCrc`] for an example.
+    #[inline(always)]
+    pub fn get(&self) -> u64 {
+        crc::register_to_checksum(self.register, &A::PARAMS)
+    }
+
+    /// Begins computation of rolling checksum.
+    ///
+    /// - Sets `window_size` to size of the given initial window.
+    ///   (`window_size` remains same during rolling.)
+    /// - Allocates and initializes buffer of
+    ///   `window_size` bytes for the window contents.
+    /// - Allocates and initializes local lookup table (2 kiB).
+    /// - Computes checksum of initial window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_window` is empty.
+    ///
+    /// See [`RollingCrc`] for an example.
+    pub fn new<T: AsRef<[u8]>>(initial_window: T) -> Self {
+        let initial_window = initial_window.as_ref();
+        let window_size = initial_window.len();
+
+        if window_size == 0 {
+            panic!("initial_window is empty");
+        }
+
+        let mut crc = Crc::<A>::new();
+        crc.update(initial_window);
+
+        let mut table = Box::new([0u64; 256]);
+        Crc::<A>::fill_rolling_table(window_size, &mut table);
+
+        Self {
+            register: crc.register,
+
+            start_pos: 0,
+            data: initial_window.to_vec(),
+
+            table,
+
+            algorithm: PhantomData,
+        }
+    }
+
+    /// Rolls window forward one byte.
+    ///
+    /// - Appends the given byte to the window.
+    /// - Removes first byte of the window.
+    /// - Recomputes checksum for the new window.
+    ///
+    /// This is a fast constant time `Θ(1)` operation
+    /// which doesn't depend on the size of the window.
+    ///
+    /// See [`RollingCrc`] for an example.
+    #[inline(always)]
+    pub fn roll(&mut self, data: u8) {
+        let out = rolling_dual_crc::rotate_window(&mut self.start_pos, &mut self.data, data);
+        self.register = Crc::<A>::roll_register(self.register, data, self.table[out as usize]);
+    }
+
+    /// Rolls window forward.
+    ///
+    /// This is equivalent to calling [`roll`] for each byte of the given slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingCrc32IsoHdlc;
+    ///
+    /// let mut crc = RollingCrc32IsoHdlc::new("abc");
+    /// crc.roll_slice("de");
+    /// // checksum of "cde"
+    /// assert_eq!(crc.get(), 0x8999971F);
+    /// ```
+    ///
+    /// [`roll`]: RollingCrc::roll
+    pub fn roll_slice<T: AsRef<[u8]>>(&mut self, data: T) {
+        for byte in data.as_ref() {
+            self.roll(*byte);
+        }
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        catalogue::{Crc32Iscsi, Crc64Nvme},
+        test_util::random_data,
+        CrcParams, RollingDualCrc,
+    };
+
+    struct Crc16Ibm3740;
+
+    impl CrcAlgorithm for Crc16Ibm3740 {
+        const PARAMS: CrcParams = CrcParams {
+            width: 16,
+            poly: 0x1021,
+            init: 0xFFFF,
+            refin: false,
+            refout: false,
+            xorout: 0x0000,
+        };
+    }
+
+    fn check_roll<A: CrcAlgorithm>(window_size: usize) {
+        let data = random_data(1000);
+        let mut crc = RollingCrc::<A>::new(&data[..window_size]);
+        for pos in 0..data.len() - window_size {
+            assert_eq!(crc.get(), Crc::<A>::checksum(&data[pos..pos + window_size]));
+            crc.roll(data[pos + window_size]);
+        }
+    }
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn empty_initial_window() {
+        RollingCrc32IsoHdlc::new([]);
+    }
+
+    // ============================================================
+    // new / get
+
+    #[test]
+    fn new_get() {
+        // "check" value from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(RollingCrc32IsoHdlc::new("123456789").get(), 0xCBF43926);
+    }
+
+    // ============================================================
+    // roll / roll_slice
+
+    #[test]
+    fn roll() {
+        check_roll::<Crc32IsoHdlc>(1);
+        check_roll::<Crc32IsoHdlc>(64);
+        check_roll::<Crc64Nvme>(100);
+        check_roll::<Crc16Ibm3740>(1);
+        check_roll::<Crc16Ibm3740>(64);
+    }
+
+    #[test]
+    fn roll_same_as_rolling_dual_crc() {
+        let data = random_data(1000);
+        let mut crc = RollingCrc::<Crc32Iscsi>::new(&data[..100]);
+        let mut dual_crc = RollingDualCrc::new(&data[..100]);
+        crc.roll_slice(&data[100..]);
+        dual_crc.roll_slice(&data[100..]);
+        assert_eq!(crc.get(), dual_crc.get32() as u64);
+    }
+
+    #[test]
+    fn roll_slice() {
+        let mut crc = RollingCrc32IsoHdlc::new("abc");
+        crc.roll_slice("defgh");
+        assert_eq!(crc.get(), Crc::<Crc32IsoHdlc>::checksum("fgh"));
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{tables, DualCrc, Zeros};
+#[cfg(feature = "std")]
+use std::io::Read;
+
+#[cfg(feature = "std")]
+use crate::ReadWindows;
+use crate::{crc::fill_table_from_basis, tables, Crc32c, Crc64Xz, Zeros};
+#[cfg(feature = "alloc")]
+use crate::{window_buffer::WindowBuffer, DualCrc, RollingTables, Windows};
 
 // ======================================================================
 // RollingDualCrc - PUBLIC
@@ -27,20 +34,78 @@ use crate::{tables, DualCrc, Zeros};
 /// // checksum of "cde"
 /// assert_eq!(crc.get32(), 0x364ADB60);
 /// ```
+///
+/// Window size can also be changed with [`push`], [`pop_front`] and [`resize`]:
+///
+/// ```rust
+/// use rolling_dual_crc::RollingDualCrc;
+///
+/// let mut crc = RollingDualCrc::new("abc");
+///
+/// crc.push(b'd');
+/// // checksum of "abcd"
+/// assert_eq!(crc.get32(), 0x92C80A31);
+///
+/// crc.pop_front();
+/// crc.pop_front();
+/// // checksum of "cd"
+/// assert_eq!(crc.get32(), RollingDualCrc::new("cd").get32());
+/// ```
+///
+/// [`pop_front`]: RollingDualCrc::pop_front
+/// [`push`]: RollingDualCrc::push
+/// [`resize`]: RollingDualCrc::resize
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug)]
 pub struct RollingDualCrc {
     inverted_crc32: u32,
     inverted_crc64: u64,
+    // window size of `tables`, kept next to checksums so that
+    // checking it in `roll` touches only the hot part of `self`
+    tables_window_size: usize,
 
-    start_pos: usize,
-    window_size: usize,
-    data: Vec<u8>,
+    data: WindowBuffer,
+    position: u64,
 
-    table32: Box<[u32; 256]>,
-    table64: Box<[u64; 256]>,
+    tables: RollingTables,
 }
 
+#[cfg(feature = "alloc")]
 impl RollingDualCrc {
+    /// Copies contents of the current window to given slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if size of `target` differs from [`window_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// crc.roll(b'd');
+    ///
+    /// let mut window = [0u8; 3];
+    /// crc.copy_window_to(&mut window);
+    /// assert_eq!(&window, b"bcd");
+    /// ```
+    ///
+    /// [`window_size`]: RollingDualCrc::window_size
+    pub fn copy_window_to(&self, target: &mut [u8]) {
+        if target.len() != self.data.len() {
+            panic!(
+                "target size {} differs from window_size {}",
+                target.len(),
+                self.data.len()
+            );
+        }
+
+        let (front, back) = self.data.as_slices();
+        target[..front.len()].copy_from_slice(front);
+        target[front.len()..].copy_from_slice(back);
+    }
+
     /// Returns 32-bit `CRC-32C` and 64-bit `CRC-64/XZ` checksums
     /// of the current window.
     ///
@@ -97,10 +162,12 @@ impl RollingDualCrc {
     /// rolling checksums.
     ///
     /// - Sets `window_size` to size of the given initial window.
-    ///   (`window_size` remains same during rolling.)
+    ///   (`window_size` remains same during rolling,
+    ///   but can be changed with [`push`], [`pop_front`] and [`resize`].)
     /// - Allocates and initializes buffer of
     ///   `window_size` bytes for the window contents.
     /// - Allocates and initializes local lookup tables (3 kiB total).
+    ///   Use [`with_tables`] to share tables between instances instead.
     /// - Computes checksums of initial window.
     ///
     /// # Panics
@@ -116,27 +183,157 @@ impl RollingDualCrc {
     /// // checksum of "abc"
     /// assert_eq!(crc.get32(), 0x364B3FB7);
     /// ```
+    ///
+    /// [`pop_front`]: RollingDualCrc::pop_front
+    /// [`push`]: RollingDualCrc::push
+    /// [`resize`]: RollingDualCrc::resize
+    /// [`with_tables`]: RollingDualCrc::with_tables
     pub fn new<T: AsRef<[u8]>>(initial_window: T) -> Self {
         let initial_window = initial_window.as_ref();
-        let window_size = initial_window.len();
 
-        if window_size == 0 {
+        if initial_window.is_empty() {
             panic!("initial_window is empty");
         }
 
-        let (crc32, crc64) = DualCrc::checksum(initial_window);
-        let (table32, table64) = Self::build_tables(window_size);
+        Self::with_tables(RollingTables::new(initial_window.len()), initial_window)
+    }
 
-        Self {
-            inverted_crc32: !crc32,
-            inverted_crc64: !crc64,
+    /// Removes first byte of the window and returns it,
+    /// or returns `None` if the window is empty.
+    ///
+    /// Checksums are updated for the smaller window
+    /// without recomputing them from window contents.
+    ///
+    /// Complexity: `Θ(1)` time if the window was one byte larger than
+    /// [`RollingTables::window_size`] (e.g. after [`push`]),
+    /// otherwise `Θ(log2 n)` time
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// assert_eq!(crc.pop_front(), Some(b'a'));
+    /// // checksums of "bc"
+    /// assert_eq!(crc.get(), RollingDualCrc::new("bc").get());
+    /// ```
+    ///
+    /// [`push`]: RollingDualCrc::push
+    pub fn pop_front(&mut self) -> Option<u8> {
+        let out = *self.data.as_slices().0.first()?;
+        self.remove_front(1);
+        Some(out)
+    }
 
-            start_pos: 0,
-            window_size,
-            data: initial_window.to_vec(),
+    /// Returns total number of bytes removed from the window so far,
+    /// by rolling or otherwise.
+    ///
+    /// This is the offset of the current window in the input data
+    /// when initial window started at offset `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// crc.roll_slice("defgh");
+    /// assert_eq!(crc.position(), 5);
+    ///
+    /// crc.pop_front();
+    /// assert_eq!(crc.position(), 6);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Appends the given byte to the window without removing anything,
+    /// growing the window by one byte.
+    ///
+    /// Complexity: `Θ(1)` time
+    ///
+    /// Next [`roll`] rebuilds local lookup tables for the new window size,
+    /// unless the window size is changed back before that.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// crc.push(b'd');
+    /// // checksums of "abcd"
+    /// assert_eq!(crc.get(), RollingDualCrc::new("abcd").get());
+    /// ```
+    ///
+    /// [`roll`]: RollingDualCrc::roll
+    pub fn push(&mut self, byte: u8) {
+        self.inverted_crc32 = tables::update_inverted_crc32(self.inverted_crc32, byte);
+        self.inverted_crc64 = tables::update_inverted_crc64(self.inverted_crc64, byte);
+        self.data.push(byte);
+    }
+
+    /// Returns iterator over checksums of every window of given size
+    /// in data from given reader.
+    ///
+    /// This is like [`windows`], but reads data in blocks,
+    /// keeping only the current window in memory.
+    /// Iterator yields `Ok((offset, crc32, crc64))` for each window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut windows = RollingDualCrc::read_windows(&b"abcde"[..], 3);
+    /// // checksums of "abc"
+    /// assert_eq!(windows.next().unwrap().unwrap(), (0, 0x364B3FB7, 0x2CD8094A1A277627));
+    /// // checksums of "bcd" and "cde"
+    /// assert_eq!(windows.count(), 2);
+    /// ```
+    ///
+    /// [`windows`]: RollingDualCrc::windows
+    #[cfg(feature = "std")]
+    pub fn read_windows<R: Read>(reader: R, window_size: usize) -> ReadWindows<R> {
+        ReadWindows::new(reader, window_size)
+    }
 
-            table32,
-            table64,
+    /// Changes size of the window to `new_size`.
+    ///
+    /// - If `new_size` is smaller than current size,
+    ///   removes bytes from the start of the window.
+    /// - If `new_size` is larger than current size,
+    ///   appends copies of `value` to the end of the window.
+    ///
+    /// Complexity: `Θ(k + log2 n)` time, where `k` is the size difference
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abcdef");
+    /// crc.resize(2, 0);
+    /// // checksums of "ef"
+    /// assert_eq!(crc.get(), RollingDualCrc::new("ef").get());
+    ///
+    /// crc.resize(4, b'x');
+    /// // checksums of "efxx"
+    /// assert_eq!(crc.get(), RollingDualCrc::new("efxx").get());
+    /// ```
+    pub fn resize(&mut self, new_size: usize, value: u8) {
+        let size = self.data.len();
+        if new_size < size {
+            self.remove_front(size - new_size);
+        } else {
+            for _ in size..new_size {
+                self.push(value);
+            }
         }
     }
 
@@ -149,6 +346,10 @@ impl RollingDualCrc {
     /// This is a fast constant time `Θ(1)` operation
     /// which doesn't depend on the size of the window.
     ///
+    /// If window size has been changed with [`push`], [`pop_front`] or [`resize`],
+    /// this first rebuilds local lookup tables for the new window size.
+    /// If the window is empty, this does nothing.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -159,19 +360,104 @@ impl RollingDualCrc {
     /// // checksum of "bcd"
     /// assert_eq!(crc.get32(), 0x1B0D0358);
     /// ```
+    ///
+    /// [`pop_front`]: RollingDualCrc::pop_front
+    /// [`push`]: RollingDualCrc::push
+    /// [`resize`]: RollingDualCrc::resize
     #[inline(always)]
     pub fn roll(&mut self, data: u8) {
-        self.inverted_crc32 = tables::update_inverted_crc32(self.inverted_crc32, data)
-            ^ self.table32[self.data[self.start_pos] as usize];
+        self.roll_evict(data);
+    }
+
+    /// Rolls window backward one byte.
+    ///
+    /// - Prepends the given byte to the window.
+    /// - Removes last byte of the window.
+    /// - Recomputes checksums for the new window.
+    ///
+    /// This undoes [`roll`] when given the byte which [`roll`] removed,
+    /// and is a constant time `Θ(1)` operation like [`roll`].
+    /// [`position`] decreases by one, wrapping around below `0`.
+    ///
+    /// If window size has been changed with [`push`], [`pop_front`] or [`resize`],
+    /// this first rebuilds local lookup tables for the new window size.
+    /// If the window is empty, this does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("bcd");
+    /// crc.roll_back(b'a');
+    /// // checksum of "abc"
+    /// assert_eq!(crc.get32(), 0x364B3FB7);
+    /// ```
+    ///
+    /// [`pop_front`]: RollingDualCrc::pop_front
+    /// [`position`]: RollingDualCrc::position
+    /// [`push`]: RollingDualCrc::push
+    /// [`resize`]: RollingDualCrc::resize
+    /// [`roll`]: RollingDualCrc::roll
+    pub fn roll_back(&mut self, data: u8) {
+        if self.data.len() != self.tables_window_size {
+            if self.data.is_empty() {
+                return;
+            }
+            self.rebuild_tables();
+        }
 
-        self.inverted_crc64 = tables::update_inverted_crc64(self.inverted_crc64, data)
-            ^ self.table64[self.data[self.start_pos] as usize];
+        // With window `W' ++ [out]`, XORing out `out` gives checksum of `W' ++ [0]`,
+        // XORing local lookup table entry of `data` gives `[data] ++ W' ++ [0]`,
+        // and shifting back one `0u8` gives the new window `[data] ++ W'`.
+        let out = self.data.roll_back(data);
+        self.inverted_crc32 = tables::unshift_inverted_crc32(
+            self.inverted_crc32
+                ^ tables::update_inverted_crc32(0, out)
+                ^ self.tables.table32()[data as usize],
+        );
+        self.inverted_crc64 = tables::unshift_inverted_crc64(
+            self.inverted_crc64
+                ^ tables::update_inverted_crc64(0, out)
+                ^ self.tables.table64()[data as usize],
+        );
+        self.position = self.position.wrapping_sub(1);
+    }
 
-        self.data[self.start_pos] = data;
-        self.start_pos += 1;
-        if self.start_pos == self.window_size {
-            self.start_pos = 0;
+    /// Rolls window forward one byte and returns the byte removed from the window,
+    /// or returns `None` if the window is empty.
+    ///
+    /// This is same as [`roll`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// assert_eq!(crc.roll_evict(b'd'), Some(b'a'));
+    /// // checksum of "bcd"
+    /// assert_eq!(crc.get32(), 0x1B0D0358);
+    /// ```
+    ///
+    /// [`roll`]: RollingDualCrc::roll
+    #[inline(always)]
+    pub fn roll_evict(&mut self, data: u8) -> Option<u8> {
+        if self.data.len() != self.tables_window_size {
+            return self.roll_evict_resized(data);
         }
+
+        let out = self.data.roll(data);
+        roll(
+            &mut self.inverted_crc32,
+            &mut self.inverted_crc64,
+            self.tables.table32(),
+            self.tables.table64(),
+            data,
+            out,
+        );
+        self.position += 1;
+        Some(out)
     }
 
     /// Rolls window forward.
@@ -195,47 +481,497 @@ impl RollingDualCrc {
             self.roll(*byte);
         }
     }
+
+    /// Rolls window forward, like [`roll_slice`],
+    /// but optimized for when checksums are needed only after the whole slice.
+    ///
+    /// Instead of rolling each byte through the window, checksums are
+    /// computed 8 bytes at a time:
+    ///
+    /// - If the slice is at least window size `n`, checksums are computed
+    ///   directly from the last `n` bytes of the slice with [`DualCrc::checksum`].
+    /// - Otherwise bytes are appended with slicing-by-8 like [`DualCrc::update`],
+    ///   and removed bytes are XORed out with 8 local lookup tables,
+    ///   one per offset in the 8-byte block.
+    ///   These tables (24 kiB total) are built on first use and kept in
+    ///   [`RollingTables`], so they are shared like the other local tables.
+    ///   Without feature `std` they aren't available
+    ///   and bytes are rolled one at a time like with [`roll_slice`].
+    ///
+    /// Complexity: `Θ(min(k, n))` time, where `k` is size of the slice
+    /// (`Θ(k)` without feature `std`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// crc.roll_slice_final("de");
+    /// // checksum of "cde"
+    /// assert_eq!(crc.get32(), 0x364ADB60);
+    /// ```
+    ///
+    /// [`roll_slice`]: RollingDualCrc::roll_slice
+    /// [`DualCrc::checksum`]: crate::DualCrc::checksum
+    /// [`DualCrc::update`]: crate::DualCrc::update
+    /// [`RollingTables`]: crate::RollingTables
+    pub fn roll_slice_final<T: AsRef<[u8]>>(&mut self, data: T) {
+        let data = data.as_ref();
+        let size = self.data.len();
+
+        if size == 0 {
+            return self.roll_slice(data);
+        }
+
+        if data.len() >= size {
+            let (crc32, crc64) = DualCrc::checksum(&data[data.len() - size..]);
+            self.inverted_crc32 = !crc32;
+            self.inverted_crc64 = !crc64;
+            self.data.roll_slice(data);
+            self.position += data.len() as u64;
+            return;
+        }
+
+        #[cfg(feature = "std")]
+        let data = self.roll_slice_blocks(data);
+        self.roll_slice(data);
+    }
+
+    /// Rolls window forward by `count` `0u8`:s.
+    ///
+    /// This is equivalent to calling [`roll`]`(0)` `count` times,
+    /// but checksums are updated with [`Zeros`] instead of
+    /// rolling each byte through the window:
+    ///
+    /// - If `count` is smaller than window size `n`, appended `0u8`:s are
+    ///   handled with [`Zeros`] and removed bytes in bulk.
+    /// - Otherwise window becomes all `0u8`:s and its checksums
+    ///   are computed directly with [`Zeros`].
+    ///
+    /// Complexity: `Θ(min(count, n) + one_bits n)` time
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abcde");
+    /// crc.roll_zeros(2);
+    /// // checksums of "cde\0\0"
+    /// assert_eq!(crc.get(), RollingDualCrc::new("cde\0\0").get());
+    ///
+    /// crc.roll_zeros(1_000_000);
+    /// assert_eq!(crc.get(), RollingDualCrc::new([0; 5]).get());
+    /// assert_eq!(crc.position(), 1_000_002);
+    /// ```
+    ///
+    /// [`roll`]: RollingDualCrc::roll
+    /// [`Zeros`]: crate::Zeros
+    pub fn roll_zeros(&mut self, count: usize) {
+        let size = self.data.len();
+        if size == 0 || count == 0 {
+            return;
+        }
+
+        if count < size {
+            self.xor_out_front(count, size - count);
+            let zeros = Zeros::new(count);
+            self.inverted_crc32 = zeros.apply_to_inverted_crc32(self.inverted_crc32);
+            self.inverted_crc64 = zeros.apply_to_inverted_crc64(self.inverted_crc64);
+        } else {
+            let mut zero_crc = DualCrc::new();
+            zero_crc.update_with_zeros(&Zeros::new(size));
+            self.inverted_crc32 = !zero_crc.get32();
+            self.inverted_crc64 = !zero_crc.get64();
+        }
+
+        for _ in 0..count.min(size) {
+            self.data.roll(0);
+        }
+        self.position += count as u64;
+    }
+
+    /// Returns local lookup tables used by this [`RollingDualCrc`].
+    ///
+    /// These can be given to [`with_tables`] to create
+    /// another [`RollingDualCrc`] with same window size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let a = RollingDualCrc::new("abc");
+    /// let b = RollingDualCrc::with_tables(a.tables().clone(), "xyz");
+    /// assert_eq!(b.get(), RollingDualCrc::new("xyz").get());
+    /// ```
+    ///
+    /// [`with_tables`]: RollingDualCrc::with_tables
+    pub fn tables(&self) -> &RollingTables {
+        &self.tables
+    }
+
+    /// Returns contents of the current window as two slices, in order.
+    ///
+    /// Window is stored in a ring buffer, so it generally isn't contiguous.
+    /// See [`copy_window_to`] for getting a contiguous copy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// crc.roll(b'd');
+    ///
+    /// let (front, back) = crc.window();
+    /// assert_eq!([front, back].concat(), b"bcd");
+    /// ```
+    ///
+    /// [`copy_window_to`]: RollingDualCrc::copy_window_to
+    pub fn window(&self) -> (&[u8], &[u8]) {
+        self.data.as_slices()
+    }
+
+    /// Returns current size of the window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let mut crc = RollingDualCrc::new("abc");
+    /// assert_eq!(crc.window_size(), 3);
+    ///
+    /// crc.push(b'd');
+    /// assert_eq!(crc.window_size(), 4);
+    /// ```
+    pub fn window_size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns iterator over checksums of every window of given size in given data.
+    ///
+    /// Iterator yields `(offset, crc32, crc64)` for each window
+    /// `data[offset..offset + window_size]`, in order.
+    /// If `data` is shorter than `window_size`, iterator is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingDualCrc;
+    ///
+    /// let windows: Vec<(usize, u32, u64)> = RollingDualCrc::windows(b"abcde", 3).collect();
+    /// assert_eq!(
+    ///     windows,
+    ///     [
+    ///         (0, 0x364B3FB7, 0x2CD8094A1A277627), // "abc"
+    ///         (1, 0x1B0D0358, 0x0557EA6AA1219070), // "bcd"
+    ///         (2, 0x364ADB60, 0xB534844A0AD06B72), // "cde"
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// See [`read_windows`] for reading data from [`Read`].
+    ///
+    #[cfg_attr(
+        feature = "std",
+        doc = "[`read_windows`]: RollingDualCrc::read_windows"
+    )]
+    #[cfg_attr(
+        not(feature = "std"),
+        doc = "[`read_windows`]: https://docs.rs/rolling-dual-crc/0.1.0/rolling_dual_crc/struct.RollingDualCrc.html#method.read_windows"
+    )]
+    /// [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    pub fn windows(data: &[u8], window_size: usize) -> Windows<'_> {
+        Windows::new(data, window_size)
+    }
+
+    /// Begins computation of 32-bit `CRC-32C` and 64-bit `CRC-64/XZ`
+    /// rolling checksums using given local lookup tables.
+    ///
+    /// This is like [`new`], but doesn't build new tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if size of `initial_window` differs from
+    /// [`RollingTables::window_size`].
+    ///
+    /// See [`RollingTables`] for an example.
+    ///
+    /// [`new`]: RollingDualCrc::new
+    pub fn with_tables<T: AsRef<[u8]>>(tables: RollingTables, initial_window: T) -> Self {
+        let initial_window = initial_window.as_ref();
+
+        if initial_window.len() != tables.window_size() {
+            panic!(
+                "initial_window size {} differs from tables window_size {}",
+                initial_window.len(),
+                tables.window_size()
+            );
+        }
+
+        let (crc32, crc64) = DualCrc::checksum(initial_window);
+
+        Self {
+            inverted_crc32: !crc32,
+            inverted_crc64: !crc64,
+            tables_window_size: tables.window_size(),
+
+            data: WindowBuffer::new(initial_window),
+            position: 0,
+
+            tables,
+        }
+    }
 }
 
 // ======================================================================
 // RollingDualCrc - PRIVATE
 
+#[cfg(feature = "alloc")]
 impl RollingDualCrc {
-    fn build_tables(window_size: usize) -> (Box<[u32; 256]>, Box<[u64; 256]>) {
-        let mut table32 = Box::new([0u32; 256]);
-        let mut table64 = Box::new([0u64; 256]);
+    /// Returns checksums of first `count` bytes of the window.
+    fn front_crc(&self, count: usize) -> DualCrc {
+        let mut crc = DualCrc::new();
+        let (front, back) = self.data.as_slices();
+        if count <= front.len() {
+            crc.update(&front[..count]);
+        } else {
+            crc.update(front);
+            crc.update(&back[..count - front.len()]);
+        }
+        crc
+    }
+
+    /// Rebuilds local lookup tables for current window size,
+    /// which must not be zero.
+    fn rebuild_tables(&mut self) {
+        self.tables = RollingTables::new(self.data.len());
+        self.tables_window_size = self.data.len();
+    }
+
+    /// Rebuilds local lookup tables for changed window size and rolls.
+    #[cold]
+    #[inline(never)]
+    fn roll_evict_resized(&mut self, data: u8) -> Option<u8> {
+        if self.data.is_empty() {
+            return None;
+        }
+        self.rebuild_tables();
+        self.roll_evict(data)
+    }
+
+    /// Removes first `count` bytes of the window.
+    fn remove_front(&mut self, count: usize) {
+        let remaining = self.data.len() - count;
+
+        if count == 1 && remaining == self.tables_window_size {
+            let out = self.data.pop_front().unwrap() as usize;
+            self.inverted_crc32 ^= self.tables.table32()[out];
+            self.inverted_crc64 ^= self.tables.table64()[out];
+            self.position += 1;
+            return;
+        }
+
+        self.xor_out_front(count, remaining);
+        self.data.remove_front(count);
+        self.position += count as u64;
+    }
+
+    /// Rolls window forward by whole 8-byte blocks of given slice,
+    /// which must be shorter than the window, and returns the remaining bytes.
+    ///
+    /// See [`roll_slice_final`](RollingDualCrc::roll_slice_final).
+    #[cfg(feature = "std")]
+    fn roll_slice_blocks<'a>(&mut self, data: &'a [u8]) -> &'a [u8] {
+        let block_len = data.len() & !7;
+        if block_len == 0 {
+            return data;
+        }
+
+        if self.data.len() != self.tables_window_size {
+            self.rebuild_tables();
+        }
+        let slice_tables = self.tables.slice_tables();
+
+        // Each block removes next 8 bytes of the original window,
+        // since slice is shorter than the window.
+        let mut crc32 = self.inverted_crc32;
+        let mut crc64 = self.inverted_crc64;
+        for (block, chunk) in data[..block_len].chunks_exact(8).enumerate() {
+            crc32 = tables::update_inverted_crc32_8bytes(crc32, chunk) ^ slice_tables.constant32;
+            crc64 = tables::update_inverted_crc64_8bytes(crc64, chunk) ^ slice_tables.constant64;
+            for offset in 0..8 {
+                let out = self.data.get(block * 8 + offset) as usize;
+                crc32 ^= slice_tables.out32[offset][out];
+                crc64 ^= slice_tables.out64[offset][out];
+            }
+        }
+        self.inverted_crc32 = crc32;
+        self.inverted_crc64 = crc64;
 
-        let zeros = Zeros::new(window_size);
+        self.data.roll_slice(&data[..block_len]);
+        self.position += block_len as u64;
+        &data[block_len..]
+    }
+
+    /// XORs first `count` bytes of the window out of checksums,
+    /// when they are followed by `following` other bytes.
+    fn xor_out_front(&mut self, count: usize, following: usize) {
+        // Removed bytes followed by `following` `0u8`:s are XORed out of checksums,
+        // like with local lookup tables but computed on demand.
+        let zeros = Zeros::new(following);
+
+        let mut removed_crc = self.front_crc(count);
+        removed_crc.update_with_zeros(&zeros);
 
         let mut zero_crc = DualCrc::new();
         zero_crc.update_with_zeros(&zeros);
 
-        for byte in 0..=255 {
-            let mut byte_crc = DualCrc::new();
-            byte_crc.update(&[byte]);
-            byte_crc.update_with_zeros(&zeros);
-            table32[byte as usize] = byte_crc.get32() ^ zero_crc.get32();
-            table64[byte as usize] = byte_crc.get64() ^ zero_crc.get64();
-        }
+        self.inverted_crc32 ^= removed_crc.get32() ^ zero_crc.get32();
+        self.inverted_crc64 ^= removed_crc.get64() ^ zero_crc.get64();
+    }
+}
 
-        (table32, table64)
+// ======================================================================
+// FUNCTIONS - CRATE
+
+/// Fills local lookup tables for rolling window of given size.
+///
+/// Tables contain contribution of each possible byte
+/// followed by `window_size` `0u8`:s, i.e. what is removed from
+/// checksum when that byte leaves the window.
+pub(crate) fn build_tables(window_size: usize, table32: &mut [u32; 256], table64: &mut [u64; 256]) {
+    let zeros = Zeros::new(window_size);
+    build_table32(&zeros, table32);
+    build_table64(&zeros, table64);
+}
+
+/// Fills local `CRC-32C` lookup table, see [`build_tables`].
+///
+/// Only entries `0`, `1`, `2`, `4`, ..., `128` are computed with [`Zeros`],
+/// see [`fill_table_from_basis`].
+pub(crate) fn build_table32(zeros: &Zeros, table32: &mut [u32; 256]) {
+    let mut zero_crc = Crc32c::new();
+    zero_crc.update_with_zeros(zeros);
+
+    for byte in [0, 1, 2, 4, 8, 16, 32, 64, 128] {
+        let mut byte_crc = Crc32c::new();
+        byte_crc.update([byte]);
+        byte_crc.update_with_zeros(zeros);
+        table32[byte as usize] = byte_crc.get() ^ zero_crc.get();
     }
+
+    fill_table_from_basis(table32);
+}
+
+/// Fills local `CRC-64/XZ` lookup table, see [`build_tables`].
+///
+/// Only entries `0`, `1`, `2`, `4`, ..., `128` are computed with [`Zeros`],
+/// see [`fill_table_from_basis`].
+pub(crate) fn build_table64(zeros: &Zeros, table64: &mut [u64; 256]) {
+    let mut zero_crc = Crc64Xz::new();
+    zero_crc.update_with_zeros(zeros);
+
+    for byte in [0, 1, 2, 4, 8, 16, 32, 64, 128] {
+        let mut byte_crc = Crc64Xz::new();
+        byte_crc.update([byte]);
+        byte_crc.update_with_zeros(zeros);
+        table64[byte as usize] = byte_crc.get() ^ zero_crc.get();
+    }
+
+    fill_table_from_basis(table64);
+}
+
+/// Updates checksums for rolling window forward one byte,
+/// with `byte` appended to and `out` removed from the window.
+#[inline(always)]
+pub(crate) fn roll(
+    inverted_crc32: &mut u32,
+    inverted_crc64: &mut u64,
+    table32: &[u32; 256],
+    table64: &[u64; 256],
+    byte: u8,
+    out: u8,
+) {
+    *inverted_crc32 = tables::update_inverted_crc32(*inverted_crc32, byte) ^ table32[out as usize];
+    *inverted_crc64 = tables::update_inverted_crc64(*inverted_crc64, byte) ^ table64[out as usize];
+}
+
+/// Replaces first byte of the window with given byte,
+/// which then becomes last byte of the window, and returns the replaced byte.
+///
+/// `data` is a ring buffer of window contents starting at `start_pos`.
+#[inline(always)]
+pub(crate) fn rotate_window(start_pos: &mut usize, data: &mut [u8], byte: u8) -> u8 {
+    let out = data[*start_pos];
+    data[*start_pos] = byte;
+    *start_pos += 1;
+    if *start_pos == data.len() {
+        *start_pos = 0;
+    }
+    out
 }
 
 // ======================================================================
 // TESTS
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
+    use alloc::collections::VecDeque;
+
+    use crate::test_util::random_data;
+
     // ============================================================
     // PANICS
 
+    #[test]
+    #[should_panic]
+    fn copy_window_to_wrong_size() {
+        RollingDualCrc::new("abc").copy_window_to(&mut [0u8; 2]);
+    }
+
     #[test]
     #[should_panic]
     fn empty_initial_window() {
-        RollingDualCrc::new(&[]);
+        RollingDualCrc::new([]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_tables_wrong_window_size() {
+        RollingDualCrc::with_tables(RollingTables::new(3), "abcd");
+    }
+
+    // ============================================================
+    // build_tables
+
+    #[test]
+    fn build_tables_same_as_direct() {
+        for window_size in [1, 2, 3, 1000] {
+            let mut table32 = [0; 256];
+            let mut table64 = [0; 256];
+            build_tables(window_size, &mut table32, &mut table64);
+
+            let zeros = Zeros::new(window_size);
+            let mut zero_crc = DualCrc::new();
+            zero_crc.update_with_zeros(&zeros);
+            for byte in 0..=255u8 {
+                let mut byte_crc = DualCrc::new();
+                byte_crc.update([byte]);
+                byte_crc.update_with_zeros(&zeros);
+                assert_eq!(table32[byte as usize], byte_crc.get32() ^ zero_crc.get32());
+                assert_eq!(table64[byte as usize], byte_crc.get64() ^ zero_crc.get64());
+            }
+        }
     }
 
     // ============================================================
@@ -311,6 +1047,223 @@ mod tests {
         }
     }
 
+    #[test]
+    fn roll_with_shared_tables() {
+        let tables = RollingTables::new(3);
+        let mut a = RollingDualCrc::with_tables(tables.clone(), "abc");
+        let mut b = RollingDualCrc::with_tables(tables, "abc");
+        assert_eq!(a.get(), (0x364B3FB7, 0x2CD8094A1A277627));
+        a.roll(b'd');
+        assert_eq!(a.get(), (0x1B0D0358, 0x0557EA6AA1219070));
+        b.roll_slice("defgh");
+        assert_eq!(b.get(), (0x861A094E, 0xB47462AF38541FB8));
+    }
+
+    // ============================================================
+    // push / pop_front / resize
+
+    #[test]
+    fn push_pop_front_roll() {
+        let data = random_data(1000);
+        let mut crc = RollingDualCrc::new(&data[..10]);
+        let mut window: VecDeque<u8> = data[..10].iter().copied().collect();
+
+        // pseudo-random mix of operations with window size 0 ..= 20
+        for (n, byte) in data[10..].iter().enumerate() {
+            match (n * 7 + *byte as usize) % 3 {
+                0 if window.len() < 20 => {
+                    crc.push(*byte);
+                    window.push_back(*byte);
+                }
+                1 => {
+                    assert_eq!(crc.pop_front(), window.pop_front());
+                }
+                _ => {
+                    crc.roll(*byte);
+                    if window.pop_front().is_some() {
+                        window.push_back(*byte);
+                    }
+                }
+            }
+            window.make_contiguous();
+            assert_eq!(crc.get(), DualCrc::checksum(window.as_slices().0));
+        }
+    }
+
+    #[test]
+    fn pop_front_to_empty() {
+        let mut crc = RollingDualCrc::new("ab");
+        assert_eq!(crc.pop_front(), Some(b'a'));
+        assert_eq!(crc.pop_front(), Some(b'b'));
+        assert_eq!(crc.get(), (0, 0));
+        assert_eq!(crc.pop_front(), None);
+        crc.roll(b'c');
+        assert_eq!(crc.get(), (0, 0));
+        crc.push(b'c');
+        crc.roll(b'd');
+        assert_eq!(crc.get(), DualCrc::checksum("d"));
+    }
+
+    #[test]
+    fn resize() {
+        let mut crc = RollingDualCrc::new("abcdefghijklmno");
+        crc.resize(15, b'x');
+        assert_eq!(crc.get(), DualCrc::checksum("abcdefghijklmno"));
+        crc.resize(5, b'x');
+        assert_eq!(crc.get(), DualCrc::checksum("klmno"));
+        crc.resize(8, b'x');
+        assert_eq!(crc.get(), DualCrc::checksum("klmnoxxx"));
+        crc.roll_slice("pq");
+        assert_eq!(crc.get(), DualCrc::checksum("mnoxxxpq"));
+        crc.resize(0, b'x');
+        assert_eq!(crc.get(), (0, 0));
+    }
+
+    // ============================================================
+    // roll_evict / position / window / copy_window_to
+
+    #[test]
+    fn roll_evict_window() {
+        let data = random_data(1000);
+        let mut crc = RollingDualCrc::new(&data[..100]);
+        let mut target = [0u8; 100];
+
+        for pos in 0..data.len() - 100 {
+            assert_eq!(crc.position(), pos as u64);
+            assert_eq!(crc.window_size(), 100);
+
+            let (front, back) = crc.window();
+            assert_eq!([front, back].concat(), &data[pos..pos + 100]);
+            crc.copy_window_to(&mut target);
+            assert_eq!(&target, &data[pos..pos + 100]);
+
+            assert_eq!(crc.roll_evict(data[pos + 100]), Some(data[pos]));
+        }
+    }
+
+    #[test]
+    fn roll_evict_empty() {
+        let mut crc = RollingDualCrc::new("a");
+        crc.pop_front();
+        assert_eq!(crc.roll_evict(b'b'), None);
+        assert_eq!(crc.position(), 1);
+        assert_eq!(crc.window(), (&[][..], &[][..]));
+    }
+
+    // ============================================================
+    // roll_back
+
+    #[test]
+    fn roll_back_same_as_direct() {
+        let data = random_data(1000);
+        for window_size in [1, 2, 100] {
+            let end = data.len() - window_size;
+            let mut crc = RollingDualCrc::new(&data[end..]);
+            for pos in (0..end).rev() {
+                crc.roll_back(data[pos]);
+                assert_eq!(crc.get(), DualCrc::checksum(&data[pos..pos + window_size]));
+            }
+            let (front, back) = crc.window();
+            assert_eq!([front, back].concat(), &data[..window_size]);
+            assert_eq!(crc.position(), 0u64.wrapping_sub(end as u64));
+        }
+    }
+
+    #[test]
+    fn roll_back_undoes_roll() {
+        let data = random_data(200);
+        let mut crc = RollingDualCrc::new(&data[..50]);
+        crc.push(1);
+        crc.roll_slice(&data[50..]);
+        let expected = crc.get();
+
+        let out = crc.roll_evict(b'x').unwrap();
+        crc.roll_back(out);
+        assert_eq!(crc.get(), expected);
+        assert_eq!(crc.position(), 150);
+    }
+
+    // ============================================================
+    // roll_slice_final
+
+    #[test]
+    fn roll_slice_final_same_as_roll_slice() {
+        let data = random_data(4000);
+        for len in [0, 1, 7, 8, 9, 63, 64, 65, 200, 999, 1000, 1001, 2000] {
+            let mut crc = RollingDualCrc::new(&data[..1000]);
+            let mut expected = RollingDualCrc::new(&data[..1000]);
+            // make window non-contiguous
+            crc.roll_slice(&data[1000..1100]);
+            expected.roll_slice(&data[1000..1100]);
+
+            crc.roll_slice_final(&data[1100..1100 + len]);
+            expected.roll_slice(&data[1100..1100 + len]);
+            assert_eq!(crc.get(), expected.get());
+            assert_eq!(crc.position(), expected.position());
+
+            // rolling continues normally
+            crc.roll_slice(&data[3500..]);
+            expected.roll_slice(&data[3500..]);
+            assert_eq!(crc.get(), expected.get());
+        }
+    }
+
+    #[test]
+    fn roll_slice_final_after_resize() {
+        let data = random_data(1000);
+        let mut crc = RollingDualCrc::new(&data[..100]);
+        crc.resize(200, 0);
+        crc.roll_slice_final(&data[100..400]);
+        assert_eq!(crc.get(), DualCrc::checksum(&data[200..400]));
+        crc.pop_front();
+        crc.roll_slice_final(&data[400..500]);
+        assert_eq!(crc.get(), DualCrc::checksum(&data[301..500]));
+        crc.push(data[500]);
+        crc.roll_slice_final(&data[501..600]);
+        assert_eq!(crc.get(), DualCrc::checksum(&data[400..600]));
+    }
+
+    // ============================================================
+    // roll_zeros
+
+    #[test]
+    fn roll_zeros_same_as_roll() {
+        let data = random_data(100);
+        for count in [1, 2, 49, 50, 51, 1000] {
+            let mut crc = RollingDualCrc::new(&data[..50]);
+            let mut expected = RollingDualCrc::new(&data[..50]);
+            crc.roll_slice(&data[50..]);
+            expected.roll_slice(&data[50..]);
+
+            crc.roll_zeros(count);
+            for _ in 0..count {
+                expected.roll(0);
+            }
+            assert_eq!(crc.get(), expected.get());
+            let (front, back) = crc.window();
+            let (expected_front, expected_back) = expected.window();
+            assert_eq!(
+                [front, back].concat(),
+                [expected_front, expected_back].concat()
+            );
+            assert_eq!(crc.position(), expected.position());
+
+            // rolling continues normally
+            crc.roll_slice(&data[..10]);
+            expected.roll_slice(&data[..10]);
+            assert_eq!(crc.get(), expected.get());
+        }
+    }
+
+    #[test]
+    fn roll_zeros_empty() {
+        let mut crc = RollingDualCrc::new("a");
+        crc.pop_front();
+        crc.roll_zeros(10);
+        assert_eq!(crc.get(), (0, 0));
+        assert_eq!(crc.position(), 1);
+    }
+
     // ============================================================
     // roll_slice
 
@@ -319,7 +1272,7 @@ mod tests {
         // All checksums here have been confirmed with `crc` crate
         let mut crc = RollingDualCrc::new("abc");
         assert_eq!(crc.get(), (0x364B3FB7, 0x2CD8094A1A277627));
-        crc.roll_slice(&[]);
+        crc.roll_slice([]);
         assert_eq!(crc.get(), (0x364B3FB7, 0x2CD8094A1A277627));
     }
 
@@ -0,0 +1,324 @@
+use std::{
+    io::{self, Read},
+    ops::ControlFlow,
+};
+
+use alloc::{vec, vec::Vec};
+
+use crate::RollingDualCrc;
+
+// ======================================================================
+// CONST - PRIVATE
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+// ======================================================================
+// RollingReader - PUBLIC
+
+/// Rolls [`RollingDualCrc`] through data from [`Read`].
+///
+/// Data is read in blocks, keeping only one block and the current window
+/// in memory, so this works for inputs of any size.
+///
+/// - [`next_window`] fills the initial window on first call
+///   and then rolls window forward one byte on each call.
+/// - [`scan`] calls a closure for every window until it returns
+///   [`ControlFlow::Break`].
+///
+/// On I/O error `Err` is returned and reading can be retried with next call.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::RollingReader;
+///
+/// let mut reader = RollingReader::new(&b"abcde"[..], 3);
+///
+/// // checksums of "abc", "bcd" and "cde"
+/// assert_eq!(reader.next_window().unwrap(), Some((0, 0x364B3FB7, 0x2CD8094A1A277627)));
+/// assert_eq!(reader.next_window().unwrap(), Some((1, 0x1B0D0358, 0x0557EA6AA1219070)));
+/// assert_eq!(reader.next_window().unwrap(), Some((2, 0x364ADB60, 0xB534844A0AD06B72)));
+/// assert_eq!(reader.next_window().unwrap(), None);
+/// ```
+///
+/// [`next_window`]: RollingReader::next_window
+/// [`scan`]: RollingReader::scan
+#[derive(Clone, Debug)]
+pub struct RollingReader<R> {
+    reader: R,
+    window_size: usize,
+
+    buffer: Vec<u8>,
+    buffer_pos: usize,
+    buffer_len: usize,
+
+    /// Initial window being read, until `crc` is created.
+    initial_window: Vec<u8>,
+    crc: Option<RollingDualCrc>,
+}
+
+impl<R> RollingReader<R> {
+    /// Returns the [`RollingDualCrc`] of the current window,
+    /// or `None` if initial window hasn't been read yet.
+    ///
+    /// This gives access to e.g. contents of the window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingReader;
+    ///
+    /// let mut reader = RollingReader::new(&b"abcde"[..], 3);
+    /// assert!(reader.crc().is_none());
+    ///
+    /// reader.next_window().unwrap();
+    /// reader.next_window().unwrap();
+    /// let (front, back) = reader.crc().unwrap().window();
+    /// assert_eq!([front, back].concat(), b"bcd");
+    /// ```
+    pub fn crc(&self) -> Option<&RollingDualCrc> {
+        self.crc.as_ref()
+    }
+
+    /// Unwraps this [`RollingReader`], returning the inner reader.
+    ///
+    /// Any data which has been read but not yet rolled is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Creates a new [`RollingReader`] with windows of given size
+    /// over data from given reader.
+    ///
+    /// Nothing is read until first call to [`next_window`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero.
+    ///
+    /// See [`RollingReader`] for an example.
+    ///
+    /// [`next_window`]: RollingReader::next_window
+    pub fn new(reader: R, window_size: usize) -> Self {
+        if window_size == 0 {
+            panic!("window_size is zero");
+        }
+
+        Self {
+            reader,
+            window_size,
+
+            buffer: vec![0u8; READ_BUFFER_SIZE],
+            buffer_pos: 0,
+            buffer_len: 0,
+
+            initial_window: Vec::with_capacity(window_size),
+            crc: None,
+        }
+    }
+}
+
+impl<R: Read> RollingReader<R> {
+    /// Moves to next window and returns `(offset, crc32, crc64)` for it,
+    /// or returns `None` at end of data.
+    ///
+    /// - First call reads the initial window at offset `0`.
+    ///   If data is shorter than `window_size`, this returns `None`.
+    /// - Further calls roll window forward one byte.
+    ///
+    /// See [`RollingReader`] for an example.
+    pub fn next_window(&mut self) -> io::Result<Option<(u64, u32, u64)>> {
+        if self.buffer_pos == self.buffer_len && !self.fill_buffer()? {
+            return Ok(None);
+        }
+
+        if let Some(crc) = &mut self.crc {
+            crc.roll(self.buffer[self.buffer_pos]);
+            self.buffer_pos += 1;
+            let (crc32, crc64) = crc.get();
+            return Ok(Some((crc.position(), crc32, crc64)));
+        }
+
+        loop {
+            let len = (self.window_size - self.initial_window.len())
+                .min(self.buffer_len - self.buffer_pos);
+            self.initial_window
+                .extend_from_slice(&self.buffer[self.buffer_pos..self.buffer_pos + len]);
+            self.buffer_pos += len;
+
+            if self.initial_window.len() == self.window_size {
+                let crc = RollingDualCrc::new(&self.initial_window);
+                self.initial_window = Vec::new();
+                let (crc32, crc64) = crc.get();
+                self.crc = Some(crc);
+                return Ok(Some((0, crc32, crc64)));
+            }
+
+            if !self.fill_buffer()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Calls `f` with `(offset, crc32, crc64)` of every window
+    /// of given size over data from given reader.
+    ///
+    /// Returns `Some` with the value of the first [`ControlFlow::Break`]
+    /// returned by `f`, or `None` if end of data was reached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingReader;
+    /// use std::ops::ControlFlow;
+    ///
+    /// // find offset of first window with `0x1B0D0358` as `crc32`
+    /// let result = RollingReader::scan(&b"abcde"[..], 3, |offset, crc32, _| {
+    ///     if crc32 == 0x1B0D0358 {
+    ///         ControlFlow::Break(offset)
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(result.unwrap(), Some(1));
+    /// ```
+    pub fn scan<B, F>(reader: R, window_size: usize, mut f: F) -> io::Result<Option<B>>
+    where
+        F: FnMut(u64, u32, u64) -> ControlFlow<B>,
+    {
+        let mut rolling_reader = Self::new(reader, window_size);
+        while let Some((offset, crc32, crc64)) = rolling_reader.next_window()? {
+            if let ControlFlow::Break(value) = f(offset, crc32, crc64) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// ======================================================================
+// RollingReader - PRIVATE
+
+impl<R: Read> RollingReader<R> {
+    /// Reads more data to empty buffer.
+    ///
+    /// Returns `false` at end of data.
+    fn fill_buffer(&mut self) -> io::Result<bool> {
+        loop {
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return Ok(false),
+                Ok(len) => {
+                    self.buffer_pos = 0;
+                    self.buffer_len = len;
+                    return Ok(true);
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::random_data, DualCrc};
+
+    /// Reader which fails every other read.
+    struct FlakyReader<'a> {
+        data: &'a [u8],
+        fail: bool,
+    }
+
+    impl Read for FlakyReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.fail = !self.fail;
+            if self.fail {
+                return Err(io::Error::other("flaky"));
+            }
+            let len = buf.len().min(self.data.len()).min(1000);
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn zero_window_size() {
+        RollingReader::new(&b"abc"[..], 0);
+    }
+
+    // ============================================================
+    // next_window
+
+    #[test]
+    fn next_window_retries_after_error() {
+        let data = random_data(5000);
+        let mut reader = RollingReader::new(
+            FlakyReader {
+                data: &data,
+                fail: false,
+            },
+            1500,
+        );
+
+        let mut expected = RollingDualCrc::windows(&data, 1500);
+        let mut error_count = 0;
+        loop {
+            match reader.next_window() {
+                Ok(Some((offset, crc32, crc64))) => {
+                    assert_eq!(Some((offset as usize, crc32, crc64)), expected.next());
+                }
+                Ok(None) => break,
+                Err(_) => error_count += 1,
+            }
+        }
+        assert!(expected.next().is_none());
+        assert!(error_count >= 5);
+    }
+
+    #[test]
+    fn next_window_shorter_than_window() {
+        let mut reader = RollingReader::new(&b"abc"[..], 4);
+        assert_eq!(reader.next_window().unwrap(), None);
+        assert_eq!(reader.next_window().unwrap(), None);
+        assert!(reader.crc().is_none());
+    }
+
+    // ============================================================
+    // scan
+
+    #[test]
+    fn scan_stops_early() {
+        // larger than `READ_BUFFER_SIZE` to test buffer refilling
+        let data = random_data(100_000);
+        let target = DualCrc::checksum(&data[80_000..80_100]);
+
+        let mut count = 0;
+        let result = RollingReader::scan(&data[..], 100, |offset, crc32, crc64| {
+            count += 1;
+            if (crc32, crc64) == target {
+                ControlFlow::Break(offset)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(result.unwrap(), Some(80_000));
+        assert_eq!(count, 80_001);
+
+        let result = RollingReader::scan(&data[..], 100, |_, _, _| ControlFlow::<()>::Continue(()));
+        assert_eq!(result.unwrap(), None);
+    }
+}
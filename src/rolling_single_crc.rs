@@ -0,0 +1,314 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{rolling_dual_crc, tables, Crc32c, Crc64Xz, Zeros};
+
+// ======================================================================
+// RollingCrc32c - PUBLIC
+
+/// Computes 32-bit `CRC-32C` checksum
+/// in a rolling window that moves through the input data.
+///
+/// This works like [`RollingDualCrc`], but computes only `CRC-32C`,
+/// which roughly halves time per [`roll`] and size of local lookup table.
+///
+/// # Examples
+///
+/// Compute checksums of 3-byte windows of `"abcde"`,
+/// i.e. `"abc"`, `"bcd"` and `"cde"`.
+///
+/// ```rust
+/// use rolling_dual_crc::RollingCrc32c;
+///
+/// let mut crc = RollingCrc32c::new("abc");
+///
+/// // checksum of "abc"
+/// assert_eq!(crc.get(), 0x364B3FB7);
+///
+/// crc.roll(b'd');
+/// // checksum of "bcd"
+/// assert_eq!(crc.get(), 0x1B0D0358);
+///
+/// crc.roll(b'e');
+/// // checksum of "cde"
+/// assert_eq!(crc.get(), 0x364ADB60);
+/// ```
+///
+/// [`RollingDualCrc`]: crate::RollingDualCrc
+/// [`roll`]: RollingCrc32c::roll
+#[derive(Clone, Debug)]
+pub struct RollingCrc32c {
+    inverted_crc: u32,
+
+    start_pos: usize,
+    data: Vec<u8>,
+
+    table: Box<[u32; 256]>,
+}
+
+impl RollingCrc32c {
+    /// Returns 32-bit `CRC-32C` checksum of the current window.
+    ///
+    /// See [`RollingCrc32c`] for an example.
+    #[inline(always)]
+    pub fn get(&self) -> u32 {
+        !self.inverted_crc
+    }
+
+    /// Begins computation of 32-bit `CRC-32C` rolling checksum.
+    ///
+    /// - Sets `window_size` to size of the given initial window.
+    ///   (`window_size` remains same during rolling.)
+    /// - Allocates and initializes buffer of
+    ///   `window_size` bytes for the window contents.
+    /// - Allocates and initializes local lookup table (1 kiB).
+    /// - Computes checksum of initial window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_window` is empty.
+    ///
+    /// See [`RollingCrc32c`] for an example.
+    pub fn new<T: AsRef<[u8]>>(initial_window: T) -> Self {
+        let initial_window = initial_window.as_ref();
+        let window_size = initial_window.len();
+
+        if window_size == 0 {
+            panic!("initial_window is empty");
+        }
+
+        let mut table = Box::new([0u32; 256]);
+        rolling_dual_crc::build_table32(&Zeros::new(window_size), &mut table);
+
+        Self {
+            inverted_crc: !Crc32c::checksum(initial_window),
+
+            start_pos: 0,
+            data: initial_window.to_vec(),
+
+            table,
+        }
+    }
+
+    /// Rolls window forward one byte.
+    ///
+    /// - Appends the given byte to the window.
+    /// - Removes first byte of the window.
+    /// - Recomputes checksum for the new window.
+    ///
+    /// This is a fast constant time `Θ(1)` operation
+    /// which doesn't depend on the size of the window.
+    ///
+    /// See [`RollingCrc32c`] for an example.
+    #[inline(always)]
+    pub fn roll(&mut self, data: u8) {
+        let out = rolling_dual_crc::rotate_window(&mut self.start_pos, &mut self.data, data);
+        self.inverted_crc =
+            tables::update_inverted_crc32(self.inverted_crc, data) ^ self.table[out as usize];
+    }
+
+    /// Rolls window forward.
+    ///
+    /// This is equivalent to calling [`roll`] for each byte of the given slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingCrc32c;
+    ///
+    /// let mut crc = RollingCrc32c::new("abc");
+    /// crc.roll_slice("de");
+    /// // checksum of "cde"
+    /// assert_eq!(crc.get(), 0x364ADB60);
+    /// ```
+    ///
+    /// [`roll`]: RollingCrc32c::roll
+    pub fn roll_slice<T: AsRef<[u8]>>(&mut self, data: T) {
+        for byte in data.as_ref() {
+            self.roll(*byte);
+        }
+    }
+}
+
+// ======================================================================
+// RollingCrc64Xz - PUBLIC
+
+/// Computes 64-bit `CRC-64/XZ` checksum
+/// in a rolling window that moves through the input data.
+///
+/// This works like [`RollingDualCrc`], but computes only `CRC-64/XZ`,
+/// which roughly halves time per [`roll`] and reduces size of
+/// local lookup table from 3 kiB to 2 kiB.
+///
+/// # Examples
+///
+/// Compute checksums of 3-byte windows of `"abcde"`,
+/// i.e. `"abc"`, `"bcd"` and `"cde"`.
+///
+/// ```rust
+/// use rolling_dual_crc::RollingCrc64Xz;
+///
+/// let mut crc = RollingCrc64Xz::new("abc");
+///
+/// // checksum of "abc"
+/// assert_eq!(crc.get(), 0x2CD8094A1A277627);
+///
+/// crc.roll(b'd');
+/// // checksum of "bcd"
+/// assert_eq!(crc.get(), 0x0557EA6AA1219070);
+///
+/// crc.roll(b'e');
+/// // checksum of "cde"
+/// assert_eq!(crc.get(), 0xB534844A0AD06B72);
+/// ```
+///
+/// [`RollingDualCrc`]: crate::RollingDualCrc
+/// [`roll`]: RollingCrc64Xz::roll
+#[derive(Clone, Debug)]
+pub struct RollingCrc64Xz {
+    inverted_crc: u64,
+
+    start_pos: usize,
+    data: Vec<u8>,
+
+    table: Box<[u64; 256]>,
+}
+
+impl RollingCrc64Xz {
+    /// Returns 64-bit `CRC-64/XZ` checksum of the current window.
+    ///
+    /// See [`RollingCrc64Xz`] for an example.
+    #[inline(always)]
+    pub fn get(&self) -> u64 {
+        !self.inverted_crc
+    }
+
+    /// Begins computation of 64-bit `CRC-64/XZ` rolling checksum.
+    ///
+    /// - Sets `window_size` to size of the given initial window.
+    ///   (`window_size` remains same during rolling.)
+    /// - Allocates and initializes buffer of
+    ///   `window_size` bytes for the window contents.
+    /// - Allocates and initializes local lookup table (2 kiB).
+    /// - Computes checksum of initial window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_window` is empty.
+    ///
+    /// See [`RollingCrc64Xz`] for an example.
+    pub fn new<T: AsRef<[u8]>>(initial_window: T) -> Self {
+        let initial_window = initial_window.as_ref();
+        let window_size = initial_window.len();
+
+        if window_size == 0 {
+            panic!("initial_window is empty");
+        }
+
+        let mut table = Box::new([0u64; 256]);
+        rolling_dual_crc::build_table64(&Zeros::new(window_size), &mut table);
+
+        Self {
+            inverted_crc: !Crc64Xz::checksum(initial_window),
+
+            start_pos: 0,
+            data: initial_window.to_vec(),
+
+            table,
+        }
+    }
+
+    /// Rolls window forward one byte.
+    ///
+    /// - Appends the given byte to the window.
+    /// - Removes first byte of the window.
+    /// - Recomputes checksum for the new window.
+    ///
+    /// This is a fast constant time `Θ(1)` operation
+    /// which doesn't depend on the size of the window.
+    ///
+    /// See [`RollingCrc64Xz`] for an example.
+    #[inline(always)]
+    pub fn roll(&mut self, data: u8) {
+        let out = rolling_dual_crc::rotate_window(&mut self.start_pos, &mut self.data, data);
+        self.inverted_crc =
+            tables::update_inverted_crc64(self.inverted_crc, data) ^ self.table[out as usize];
+    }
+
+    /// Rolls window forward.
+    ///
+    /// This is equivalent to calling [`roll`] for each byte of the given slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingCrc64Xz;
+    ///
+    /// let mut crc = RollingCrc64Xz::new("abc");
+    /// crc.roll_slice("de");
+    /// // checksum of "cde"
+    /// assert_eq!(crc.get(), 0xB534844A0AD06B72);
+    /// ```
+    ///
+    /// [`roll`]: RollingCrc64Xz::roll
+    pub fn roll_slice<T: AsRef<[u8]>>(&mut self, data: T) {
+        for byte in data.as_ref() {
+            self.roll(*byte);
+        }
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{test_util::random_data, RollingDualCrc};
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn crc32c_empty_initial_window() {
+        RollingCrc32c::new([]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn crc64xz_empty_initial_window() {
+        RollingCrc64Xz::new([]);
+    }
+
+    // ============================================================
+    // new / roll / roll_slice
+
+    #[test]
+    fn same_as_rolling_dual_crc() {
+        let data = random_data(10_000);
+        for window_size in [1, 2, 7, 8, 9, 1024] {
+            let mut crc32 = RollingCrc32c::new(&data[..window_size]);
+            let mut crc64 = RollingCrc64Xz::new(&data[..window_size]);
+            let mut dual_crc = RollingDualCrc::new(&data[..window_size]);
+            assert_eq!((crc32.get(), crc64.get()), dual_crc.get());
+
+            for byte in &data[window_size..] {
+                crc32.roll(*byte);
+                crc64.roll(*byte);
+                dual_crc.roll(*byte);
+                assert_eq!((crc32.get(), crc64.get()), dual_crc.get());
+            }
+        }
+    }
+
+    #[test]
+    fn roll_slice() {
+        let mut crc32 = RollingCrc32c::new("abc");
+        let mut crc64 = RollingCrc64Xz::new("abc");
+        crc32.roll_slice("defgh");
+        crc64.roll_slice("defgh");
+        // checksums of "fgh"
+        assert_eq!((crc32.get(), crc64.get()), RollingDualCrc::new("fgh").get());
+    }
+}
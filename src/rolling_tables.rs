@@ -0,0 +1,237 @@
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+use crate::rolling_dual_crc;
+#[cfg(feature = "std")]
+use crate::tables;
+
+// ======================================================================
+// RollingTables - PUBLIC
+
+/// Local lookup tables of [`RollingDualCrc`] for one window size,
+/// shareable between many [`RollingDualCrc`] instances.
+///
+/// [`RollingDualCrc::new`] builds new tables (3 kiB total) every time,
+/// which dominates its cost with small windows.
+/// When many windows of same size are needed, build tables once
+/// with [`RollingTables::new`] and use [`RollingDualCrc::with_tables`] instead.
+///
+/// Tables are reference-counted, so [`Clone`] is cheap.
+/// This includes tables of [`RollingDualCrc::roll_slice_final`] (24 kiB total),
+/// which are built on its first use.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::{RollingDualCrc, RollingTables};
+///
+/// let tables = RollingTables::new(3);
+///
+/// let mut a = RollingDualCrc::with_tables(tables.clone(), "abc");
+/// let mut b = RollingDualCrc::with_tables(tables, "xyz");
+///
+/// a.roll(b'd');
+/// b.roll(b'd');
+/// assert_eq!(a.get(), RollingDualCrc::new("bcd").get());
+/// assert_eq!(b.get(), RollingDualCrc::new("yzd").get());
+/// ```
+///
+/// [`RollingDualCrc`]: crate::RollingDualCrc
+/// [`RollingDualCrc::new`]: crate::RollingDualCrc::new
+/// [`RollingDualCrc::roll_slice_final`]: crate::RollingDualCrc::roll_slice_final
+/// [`RollingDualCrc::with_tables`]: crate::RollingDualCrc::with_tables
+#[derive(Clone, Debug)]
+pub struct RollingTables {
+    // outside of `inner` so that checking it doesn't need extra indirection
+    window_size: usize,
+    inner: Arc<Inner>,
+}
+
+impl RollingTables {
+    /// Builds local lookup tables (3 kiB total) for rolling window of given size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is zero.
+    ///
+    /// See [`RollingTables`] for an example.
+    pub fn new(window_size: usize) -> Self {
+        if window_size == 0 {
+            panic!("window_size is zero");
+        }
+
+        let mut inner = Arc::new(Inner {
+            table32: [0; 256],
+            table64: [0; 256],
+            #[cfg(feature = "std")]
+            slice_tables: OnceLock::new(),
+        });
+        let tables = Arc::get_mut(&mut inner).expect("Arc is not shared");
+        rolling_dual_crc::build_tables(window_size, &mut tables.table32, &mut tables.table64);
+
+        Self { window_size, inner }
+    }
+
+    /// Returns size of the rolling window these tables are for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rolling_dual_crc::RollingTables;
+    ///
+    /// assert_eq!(RollingTables::new(1024).window_size(), 1024);
+    /// ```
+    #[inline(always)]
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+}
+
+// ======================================================================
+// RollingTables - CRATE
+
+impl RollingTables {
+    /// Returns tables for [`RollingDualCrc::roll_slice_final`],
+    /// building them on first call.
+    ///
+    /// [`RollingDualCrc::roll_slice_final`]: crate::RollingDualCrc::roll_slice_final
+    #[cfg(feature = "std")]
+    pub(crate) fn slice_tables(&self) -> &SliceTables {
+        self.inner
+            .slice_tables
+            .get_or_init(|| Box::new(SliceTables::new(self)))
+    }
+
+    #[inline(always)]
+    pub(crate) fn table32(&self) -> &[u32; 256] {
+        &self.inner.table32
+    }
+
+    #[inline(always)]
+    pub(crate) fn table64(&self) -> &[u64; 256] {
+        &self.inner.table64
+    }
+}
+
+// ======================================================================
+// SliceTables - CRATE
+
+/// Local lookup tables for rolling window of one size forward 8 bytes at a time,
+/// used by [`RollingDualCrc::roll_slice_final`] (24 kiB total).
+///
+/// Rolling 8 bytes appends them with slicing-by-8 and XORs out
+/// `out32[j][o]` / `out64[j][o]` for each removed byte `o` at offset `j`,
+/// and `constant32` / `constant64` for moving `init` forward 8 bytes.
+///
+/// [`RollingDualCrc::roll_slice_final`]: crate::RollingDualCrc::roll_slice_final
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub(crate) struct SliceTables {
+    pub(crate) constant32: u32,
+    pub(crate) constant64: u64,
+    pub(crate) out32: [[u32; 256]; 8],
+    pub(crate) out64: [[u64; 256]; 8],
+}
+
+#[cfg(feature = "std")]
+impl SliceTables {
+    /// Builds tables from [`RollingTables`] of same window size.
+    ///
+    /// Rolling table entry of byte `b` is `b` followed by `n` `0u8`:s,
+    /// plus `init` term which is same for all entries.
+    /// Entries of `out[7]` are those without `init` term,
+    /// and entries of `out[j]` are those of `out[j + 1]` followed by one more `0u8`.
+    pub(crate) fn new(rolling: &RollingTables) -> Self {
+        let table32 = rolling.table32();
+        let table64 = rolling.table64();
+
+        let mut out32 = [[0u32; 256]; 8];
+        let mut out64 = [[0u64; 256]; 8];
+        for byte in 0..256 {
+            out32[7][byte] = table32[byte] ^ table32[0];
+            out64[7][byte] = table64[byte] ^ table64[0];
+        }
+        for j in (0..7).rev() {
+            for byte in 0..256 {
+                out32[j][byte] = tables::update_inverted_crc32(out32[j + 1][byte], 0);
+                out64[j][byte] = tables::update_inverted_crc64(out64[j + 1][byte], 0);
+            }
+        }
+
+        // `init` term of rolling table moves `init` forward one byte,
+        // so moving it 8 bytes is the sum of that term shifted by 0 ..= 7 `0u8`:s.
+        let mut constant32 = 0;
+        let mut constant64 = 0;
+        let mut term32 = table32[0];
+        let mut term64 = table64[0];
+        for _ in 0..8 {
+            constant32 ^= term32;
+            constant64 ^= term64;
+            term32 = tables::update_inverted_crc32(term32, 0);
+            term64 = tables::update_inverted_crc64(term64, 0);
+        }
+
+        Self {
+            constant32,
+            constant64,
+            out32,
+            out64,
+        }
+    }
+}
+
+// ======================================================================
+// Inner - PRIVATE
+
+#[derive(Debug)]
+struct Inner {
+    table32: [u32; 256],
+    table64: [u64; 256],
+    // built on first `RollingDualCrc::roll_slice_final`
+    #[cfg(feature = "std")]
+    slice_tables: OnceLock<Box<SliceTables>>,
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn zero_window_size() {
+        RollingTables::new(0);
+    }
+
+    // ============================================================
+    // new / clone
+
+    #[test]
+    fn clone_shares_tables() {
+        let tables = RollingTables::new(16);
+        let clone = tables.clone();
+        assert!(Arc::ptr_eq(&tables.inner, &clone.inner));
+        assert_eq!(clone.window_size(), 16);
+    }
+
+    // ============================================================
+    // slice_tables
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn slice_tables_built_once_and_shared() {
+        let tables = RollingTables::new(16);
+        let clone = tables.clone();
+        assert!(core::ptr::eq(tables.slice_tables(), clone.slice_tables()));
+        assert!(core::ptr::eq(tables.slice_tables(), tables.slice_tables()));
+    }
+}
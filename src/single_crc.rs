@@ -0,0 +1,309 @@
+use crate::{tables, Zeros};
+
+// ======================================================================
+// Crc32c - PUBLIC
+
+/// Computes 32-bit `CRC-32C` checksum in one go or iteratively.
+///
+/// This works like [`DualCrc`], but computes only `CRC-32C`,
+/// which is about twice as fast when `CRC-64/XZ` isn't needed.
+///
+/// With feature `digest` this implements traits of [`digest`] crate,
+/// with output being the checksum as 4 bytes in big-endian byte order,
+/// i.e. same as the usual hexadecimal representation of the checksum.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::Crc32c;
+///
+/// assert_eq!(Crc32c::checksum("Hello, world!"), 0xC8A106E5);
+///
+/// let mut crc = Crc32c::new();
+/// crc.update("Hello");
+/// crc.update(", world!");
+/// assert_eq!(crc.get(), 0xC8A106E5);
+/// ```
+///
+/// [`DualCrc`]: crate::DualCrc
+/// [`digest`]: https://crates.io/crates/digest
+#[derive(Clone, Copy, Debug, Hash)]
+pub struct Crc32c {
+    inverted_crc: u32,
+}
+
+impl Crc32c {
+    /// Computes 32-bit `CRC-32C` checksum of given data.
+    ///
+    /// See [`Crc32c`] for an example.
+    pub fn checksum<T: AsRef<[u8]>>(data: T) -> u32 {
+        #[cfg(feature = "crc32c")]
+        return crc32c::crc32c(data.as_ref());
+
+        #[cfg(not(feature = "crc32c"))]
+        {
+            let mut crc = Self::new();
+            crc.update(data);
+            crc.get()
+        }
+    }
+
+    /// Returns 32-bit `CRC-32C` checksum of the data processed so far.
+    ///
+    /// Checksum computation is not reset and can be continued with further data.
+    ///
+    /// See [`Crc32c`] for an example.
+    #[inline(always)]
+    pub fn get(&self) -> u32 {
+        !self.inverted_crc
+    }
+
+    /// Begins computation of 32-bit `CRC-32C` checksum.
+    ///
+    /// See [`Crc32c`] for an example.
+    pub fn new() -> Self {
+        Self { inverted_crc: !0 }
+    }
+
+    /// Continues checksum computation with given data.
+    ///
+    /// See [`Crc32c`] for an example.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        #[cfg(feature = "crc32c")]
+        {
+            self.inverted_crc = !crc32c::crc32c_append(!self.inverted_crc, data.as_ref());
+        }
+
+        #[cfg(not(feature = "crc32c"))]
+        {
+            let data = data.as_ref();
+            let mut pos = 0;
+            let mut remaining = data.len();
+
+            while remaining >= 8 {
+                self.inverted_crc =
+                    tables::update_inverted_crc32_8bytes(self.inverted_crc, &data[pos..pos + 8]);
+                pos += 8;
+                remaining -= 8;
+            }
+
+            while remaining > 0 {
+                self.inverted_crc = tables::update_inverted_crc32(self.inverted_crc, data[pos]);
+                pos += 1;
+                remaining -= 1;
+            }
+        }
+    }
+
+    /// Continues checksum computation with `0u8` sequence
+    /// represented by the given [`Zeros`].
+    ///
+    /// This is equivalent to [`update`]`(&[0u8; N])`
+    /// but more efficient with long sequences.
+    ///
+    /// Complexity: `Θ(1)` time
+    ///
+    /// See [`Zeros`] for more details.
+    ///
+    /// [`update`]: Crc32c::update
+    /// [`Zeros`]: crate::Zeros
+    #[inline(always)]
+    pub fn update_with_zeros(&mut self, zeros: &Zeros) {
+        self.inverted_crc = zeros.apply_to_inverted_crc32(self.inverted_crc);
+    }
+}
+
+impl Default for Crc32c {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ======================================================================
+// Crc64Xz - PUBLIC
+
+/// Computes 64-bit `CRC-64/XZ` checksum in one go or iteratively.
+///
+/// This works like [`DualCrc`], but computes only `CRC-64/XZ`,
+/// which is faster when `CRC-32C` isn't needed.
+///
+/// With feature `digest` this implements traits of [`digest`] crate,
+/// with output being the checksum as 8 bytes in big-endian byte order,
+/// i.e. same as the usual hexadecimal representation of the checksum.
+///
+/// # Examples
+///
+/// ```rust
+/// use rolling_dual_crc::Crc64Xz;
+///
+/// assert_eq!(Crc64Xz::checksum("Hello, world!"), 0x8E59E143665877C4);
+///
+/// let mut crc = Crc64Xz::new();
+/// crc.update("Hello");
+/// crc.update(", world!");
+/// assert_eq!(crc.get(), 0x8E59E143665877C4);
+/// ```
+///
+/// [`DualCrc`]: crate::DualCrc
+/// [`digest`]: https://crates.io/crates/digest
+#[derive(Clone, Copy, Debug, Hash)]
+pub struct Crc64Xz {
+    inverted_crc: u64,
+}
+
+impl Crc64Xz {
+    /// Computes 64-bit `CRC-64/XZ` checksum of given data.
+    ///
+    /// See [`Crc64Xz`] for an example.
+    pub fn checksum<T: AsRef<[u8]>>(data: T) -> u64 {
+        #[cfg(feature = "crc64fast")]
+        {
+            let mut crc = crc64fast::Digest::new();
+            crc.write(data.as_ref());
+            crc.sum64()
+        }
+
+        #[cfg(not(feature = "crc64fast"))]
+        {
+            let mut crc = Self::new();
+            crc.update(data);
+            crc.get()
+        }
+    }
+
+    /// Returns 64-bit `CRC-64/XZ` checksum of the data processed so far.
+    ///
+    /// Checksum computation is not reset and can be continued with further data.
+    ///
+    /// See [`Crc64Xz`] for an example.
+    #[inline(always)]
+    pub fn get(&self) -> u64 {
+        !self.inverted_crc
+    }
+
+    /// Begins computation of 64-bit `CRC-64/XZ` checksum.
+    ///
+    /// See [`Crc64Xz`] for an example.
+    pub fn new() -> Self {
+        Self { inverted_crc: !0 }
+    }
+
+    /// Continues checksum computation with given data.
+    ///
+    /// See [`Crc64Xz`] for an example.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let data = data.as_ref();
+        let mut pos = 0;
+        let mut remaining = data.len();
+
+        while remaining >= 8 {
+            self.inverted_crc =
+                tables::update_inverted_crc64_8bytes(self.inverted_crc, &data[pos..pos + 8]);
+            pos += 8;
+            remaining -= 8;
+        }
+
+        while remaining > 0 {
+            self.inverted_crc = tables::update_inverted_crc64(self.inverted_crc, data[pos]);
+            pos += 1;
+            remaining -= 1;
+        }
+    }
+
+    /// Continues checksum computation with `0u8` sequence
+    /// represented by the given [`Zeros`].
+    ///
+    /// This is equivalent to [`update`]`(&[0u8; N])`
+    /// but more efficient with long sequences.
+    ///
+    /// Complexity: `Θ(1)` time
+    ///
+    /// See [`Zeros`] for more details.
+    ///
+    /// [`update`]: Crc64Xz::update
+    /// [`Zeros`]: crate::Zeros
+    #[inline(always)]
+    pub fn update_with_zeros(&mut self, zeros: &Zeros) {
+        self.inverted_crc = zeros.apply_to_inverted_crc64(self.inverted_crc);
+    }
+}
+
+impl Default for Crc64Xz {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::DualCrc;
+
+    const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog.";
+
+    // ============================================================
+    // Crc32c
+
+    #[test]
+    fn crc32c_checksum() {
+        // "check" value from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(Crc32c::checksum(b"123456789"), 0xE3069283);
+        assert_eq!(Crc32c::checksum(b""), 0);
+    }
+
+    #[test]
+    fn crc32c_update_all_splits() {
+        for split in 0..=DATA.len() {
+            let mut crc = Crc32c::new();
+            crc.update(&DATA[..split]);
+            crc.update(&DATA[split..]);
+            assert_eq!(crc.get(), DualCrc::checksum32(DATA));
+        }
+    }
+
+    #[test]
+    fn crc32c_update_with_zeros() {
+        let mut crc = Crc32c::new();
+        crc.update(b"abc");
+        crc.update_with_zeros(&Zeros::new(100));
+
+        let mut data = [0u8; 103];
+        data[..3].copy_from_slice(b"abc");
+        assert_eq!(crc.get(), Crc32c::checksum(data));
+    }
+
+    // ============================================================
+    // Crc64Xz
+
+    #[test]
+    fn crc64xz_checksum() {
+        // "check" value from "Catalogue of parametrised CRC algorithms"
+        assert_eq!(Crc64Xz::checksum(b"123456789"), 0x995DC9BBDF1939FA);
+        assert_eq!(Crc64Xz::checksum(b""), 0);
+    }
+
+    #[test]
+    fn crc64xz_update_all_splits() {
+        for split in 0..=DATA.len() {
+            let mut crc = Crc64Xz::new();
+            crc.update(&DATA[..split]);
+            crc.update(&DATA[split..]);
+            assert_eq!(crc.get(), DualCrc::checksum64(DATA));
+        }
+    }
+
+    #[test]
+    fn crc64xz_update_with_zeros() {
+        let mut crc = Crc64Xz::new();
+        crc.update(b"abc");
+        crc.update_with_zeros(&Zeros::new(100));
+
+        let mut data = [0u8; 103];
+        data[..3].copy_from_slice(b"abc");
+        assert_eq!(crc.get(), Crc64Xz::checksum(data));
+    }
+}
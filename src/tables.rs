@@ -1,3 +1,9 @@
+use crate::{
+    catalogue::{Crc32Iscsi, Crc64XzAlgo},
+    crc::{self, USIZE_BITS},
+    CrcAlgorithm,
+};
+
 // ======================================================================
 // FUNCTIONS - CRATE
 
@@ -7,7 +13,6 @@ pub(crate) fn update_inverted_crc32(inverted_crc: u32, byte: u8) -> u32 {
 }
 
 /// This is equivalent to calling `update_inverted_crc32` for each byte, but faster.
-#[cfg(not(feature = "crc32c"))]
 #[inline(always)]
 pub(crate) fn update_inverted_crc32_8bytes(mut inverted_crc: u32, data: &[u8]) -> u32 {
     debug_assert_eq!(data.len(), 8);
@@ -60,11 +65,88 @@ pub(crate) fn update_inverted_crc64_8bytes(mut inverted_crc: u64, data: &[u8]) -
         ^ CRC64[0][(inverted_crc >> 56) as usize]
 }
 
+/// Inverse of `update_inverted_crc32(inverted_crc, 0)`,
+/// i.e. multiplication by inverse of `x^8` in GF(2).
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn unshift_inverted_crc32(inverted_crc: u32) -> u32 {
+    let byte = UNSHIFT32[(inverted_crc >> 24) as usize];
+    ((inverted_crc ^ CRC32[0][byte as usize]) << 8) | byte as u32
+}
+
+/// Inverse of `update_inverted_crc64(inverted_crc, 0)`,
+/// i.e. multiplication by inverse of `x^8` in GF(2).
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn unshift_inverted_crc64(inverted_crc: u64) -> u64 {
+    let byte = UNSHIFT64[(inverted_crc >> 56) as usize];
+    ((inverted_crc ^ CRC64[0][byte as usize]) << 8) | byte as u64
+}
+
 // ======================================================================
 // STATIC - PRIVATE / CRATE
 
-// see `build.rs`
-include! {env!("GENERATED_TABLES_RS")}
+static CRC32: [[u32; 256]; 8] = narrow_tables(&crc::make_slicing_tables(&Crc32Iscsi::PARAMS));
+
+static CRC64: [[u64; 256]; 8] = crc::make_slicing_tables(&Crc64XzAlgo::PARAMS);
+
+#[cfg(feature = "alloc")]
+static UNSHIFT32: [u8; 256] = make_unshift_table(&crc::make_table(&Crc32Iscsi::PARAMS), 32);
+
+#[cfg(feature = "alloc")]
+static UNSHIFT64: [u8; 256] = make_unshift_table(&crc::make_table(&Crc64XzAlgo::PARAMS), 64);
+
+pub(crate) static POW256_32: [u32; USIZE_BITS] =
+    narrow_pow256_table(&crc::make_pow256_table(&Crc32Iscsi::PARAMS));
+
+pub(crate) static POW256_64: [u64; USIZE_BITS] = crc::make_pow256_table(&Crc64XzAlgo::PARAMS);
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+const fn narrow_tables(wide: &[[u64; 256]; 8]) -> [[u32; 256]; 8] {
+    let mut tables = [[0u32; 256]; 8];
+    let mut n = 0;
+    while n < 8 {
+        let mut byte = 0;
+        while byte < 256 {
+            tables[n][byte] = wide[n][byte] as u32;
+            byte += 1;
+        }
+        n += 1;
+    }
+    tables
+}
+
+/// Creates table from highest byte of `table` entry to its index,
+/// which undoes shifting of reflected register by one `0u8`.
+///
+/// Highest byte of entry is unique for each index,
+/// since polynomial has `x^0` term, i.e. highest bit of reflected polynomial is set.
+#[cfg(feature = "alloc")]
+const fn make_unshift_table(table: &[u64; 256], width: u32) -> [u8; 256] {
+    let mut unshift = [0u8; 256];
+    let mut seen = [false; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let top = (table[byte] >> (width - 8)) as usize & 0xFF;
+        assert!(!seen[top], "highest bytes of table entries are not unique");
+        seen[top] = true;
+        unshift[top] = byte as u8;
+        byte += 1;
+    }
+    unshift
+}
+
+const fn narrow_pow256_table(wide: &[u64; USIZE_BITS]) -> [u32; USIZE_BITS] {
+    let mut table = [0u32; USIZE_BITS];
+    let mut n = 0;
+    while n < USIZE_BITS {
+        table[n] = wide[n] as u32;
+        n += 1;
+    }
+    table
+}
 
 // ======================================================================
 // TESTS
@@ -80,7 +162,7 @@ mod tests {
         let mut crc = DualCrc::new();
         for inner in CRC32 {
             for x in inner {
-                crc.update(&x.to_le_bytes());
+                crc.update(x.to_le_bytes());
             }
         }
         // These values have been confirmed with `crc` crate
@@ -92,18 +174,42 @@ mod tests {
         let mut crc = DualCrc::new();
         for inner in CRC64 {
             for x in inner {
-                crc.update(&x.to_le_bytes());
+                crc.update(x.to_le_bytes());
             }
         }
         // These values have been confirmed with `crc` crate
         assert_eq!(crc.get(), (0x3D345BF2, 0x014ED9B63590C55E));
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn unshift_undoes_shift() {
+        for inverted_crc in [0, 1, 0x80, 0x12345678_9ABCDEF0, u64::MAX] {
+            let crc32 = inverted_crc as u32;
+            assert_eq!(
+                unshift_inverted_crc32(update_inverted_crc32(crc32, 0)),
+                crc32
+            );
+            assert_eq!(
+                update_inverted_crc32(unshift_inverted_crc32(crc32), 0),
+                crc32
+            );
+            assert_eq!(
+                unshift_inverted_crc64(update_inverted_crc64(inverted_crc, 0)),
+                inverted_crc
+            );
+            assert_eq!(
+                update_inverted_crc64(unshift_inverted_crc64(inverted_crc), 0),
+                inverted_crc
+            );
+        }
+    }
+
     #[test]
     fn pow256_32_table_checksums() {
         let mut crc = DualCrc::new();
         for x in POW256_32 {
-            crc.update(&x.to_le_bytes());
+            crc.update(x.to_le_bytes());
         }
         // These values have been confirmed with `crc` crate
         assert_eq!(crc.get(), (0xB3683DC1, 0xAB7DB56545FE470F));
@@ -113,7 +219,7 @@ mod tests {
     fn pow256_64_table_checksums() {
         let mut crc = DualCrc::new();
         for x in POW256_64 {
-            crc.update(&x.to_le_bytes());
+            crc.update(x.to_le_bytes());
         }
         // These values have been confirmed with `crc` crate
         assert_eq!(crc.get(), (0x49BABB74, 0x0F7DE3B7F5984AEF));
@@ -0,0 +1,14 @@
+use alloc::vec::Vec;
+
+/// Returns `len` bytes of deterministic pseudorandom data (xorshift64).
+pub(crate) fn random_data(len: usize) -> Vec<u8> {
+    let mut state: u64 = 0x0123_4567_89AB_CDEF;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
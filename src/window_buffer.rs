@@ -0,0 +1,225 @@
+use alloc::{vec, vec::Vec};
+
+// ======================================================================
+// WindowBuffer - CRATE
+
+/// Ring buffer of rolling window contents which can also grow and shrink.
+///
+/// Capacity is a power of two, so wrapping is a single mask
+/// and rolling costs about same as with exact-size ring buffer.
+/// (`VecDeque` was measured to make `RollingDualCrc::roll` ~40% slower.)
+#[derive(Clone, Debug)]
+pub(crate) struct WindowBuffer {
+    buf: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl WindowBuffer {
+    /// Returns window contents as two slices, in order.
+    pub(crate) fn as_slices(&self) -> (&[u8], &[u8]) {
+        let end = self.start + self.len;
+        if end <= self.buf.len() {
+            (&self.buf[self.start..end], &[])
+        } else {
+            (&self.buf[self.start..], &self.buf[..end - self.buf.len()])
+        }
+    }
+
+    /// Returns byte at given index from the start.
+    ///
+    /// Index must be less than length.
+    #[inline(always)]
+    pub(crate) fn get(&self, index: usize) -> u8 {
+        self.buf[(self.start + index) & self.mask()]
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn new(initial_window: &[u8]) -> Self {
+        let mut buf = vec![0; initial_window.len().next_power_of_two()];
+        buf[..initial_window.len()].copy_from_slice(initial_window);
+        Self {
+            buf,
+            start: 0,
+            len: initial_window.len(),
+        }
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let out = self.buf[self.start];
+        self.start = (self.start + 1) & self.mask();
+        self.len -= 1;
+        Some(out)
+    }
+
+    pub(crate) fn push(&mut self, byte: u8) {
+        if self.len == self.buf.len() {
+            self.grow();
+        }
+        let end = (self.start + self.len) & self.mask();
+        self.buf[end] = byte;
+        self.len += 1;
+    }
+
+    /// Removes first `count` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is larger than length.
+    pub(crate) fn remove_front(&mut self, count: usize) {
+        assert!(count <= self.len, "count is larger than length");
+        self.start = (self.start + count) & self.mask();
+        self.len -= count;
+    }
+
+    /// Appends given byte and removes first byte, returning it.
+    ///
+    /// Window must not be empty.
+    #[inline(always)]
+    pub(crate) fn roll(&mut self, byte: u8) -> u8 {
+        let mask = self.mask();
+        let out = self.buf[self.start];
+        self.buf[(self.start + self.len) & mask] = byte;
+        self.start = (self.start + 1) & mask;
+        out
+    }
+
+    /// Prepends given byte and removes last byte, returning it.
+    ///
+    /// Window must not be empty.
+    #[inline(always)]
+    pub(crate) fn roll_back(&mut self, byte: u8) -> u8 {
+        let mask = self.mask();
+        let out = self.buf[(self.start + self.len - 1) & mask];
+        self.start = self.start.wrapping_sub(1) & mask;
+        self.buf[self.start] = byte;
+        out
+    }
+
+    /// Appends given bytes and removes same number of bytes from the start,
+    /// keeping length same.
+    pub(crate) fn roll_slice(&mut self, data: &[u8]) {
+        if data.len() >= self.len {
+            let len = self.len;
+            self.buf[..len].copy_from_slice(&data[data.len() - len..]);
+            self.start = 0;
+            return;
+        }
+
+        let mask = self.mask();
+        let mut end = (self.start + self.len) & mask;
+        let mut rest = data;
+        while !rest.is_empty() {
+            let len = rest.len().min(self.buf.len() - end);
+            self.buf[end..end + len].copy_from_slice(&rest[..len]);
+            rest = &rest[len..];
+            end = (end + len) & mask;
+        }
+        self.start = (self.start + data.len()) & mask;
+    }
+}
+
+// ======================================================================
+// WindowBuffer - PRIVATE
+
+impl WindowBuffer {
+    fn grow(&mut self) {
+        let (front, back) = self.as_slices();
+        let mut buf = Vec::with_capacity(self.buf.len() * 2);
+        buf.extend_from_slice(front);
+        buf.extend_from_slice(back);
+        buf.resize(self.buf.len() * 2, 0);
+        self.buf = buf;
+        self.start = 0;
+    }
+
+    #[inline(always)]
+    fn mask(&self) -> usize {
+        self.buf.len() - 1
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::VecDeque;
+
+    fn contents(buffer: &WindowBuffer) -> Vec<u8> {
+        let (front, back) = buffer.as_slices();
+        [front, back].concat()
+    }
+
+    #[test]
+    fn same_as_vec_deque() {
+        let mut buffer = WindowBuffer::new(b"abc");
+        let mut expected: VecDeque<u8> = b"abc".iter().copied().collect();
+
+        for n in 0..200u8 {
+            match n % 7 {
+                0..=2 => {
+                    buffer.push(n);
+                    expected.push_back(n);
+                }
+                3 => assert_eq!(buffer.pop_front(), expected.pop_front()),
+                4 if !expected.is_empty() => {
+                    assert_eq!(Some(buffer.roll_back(n)), expected.pop_back());
+                    expected.push_front(n);
+                }
+                _ if !expected.is_empty() => {
+                    assert_eq!(Some(buffer.roll(n)), expected.pop_front());
+                    expected.push_back(n);
+                }
+                _ => {}
+            }
+            assert_eq!(buffer.len(), expected.len());
+            assert_eq!(contents(&buffer), Vec::from(expected.clone()));
+            for (index, byte) in expected.iter().enumerate() {
+                assert_eq!(buffer.get(index), *byte);
+            }
+        }
+    }
+
+    #[test]
+    fn roll_slice_same_as_roll() {
+        for initial_len in [1, 5, 8] {
+            for count in 0..20 {
+                let mut buffer = WindowBuffer::new(&[1, 2, 3, 4, 5, 6, 7, 8][..initial_len]);
+                // make window non-contiguous with extra capacity
+                buffer.push(9);
+                buffer.roll(10);
+                buffer.pop_front();
+
+                let mut expected = buffer.clone();
+                let data: Vec<u8> = (100..100 + count).collect();
+                buffer.roll_slice(&data);
+                for byte in &data {
+                    expected.roll(*byte);
+                }
+                assert_eq!(contents(&buffer), contents(&expected));
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let mut buffer = WindowBuffer::new(b"");
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.pop_front(), None);
+        buffer.push(1);
+        assert_eq!(contents(&buffer), [1]);
+    }
+}
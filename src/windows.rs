@@ -0,0 +1,175 @@
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use crate::RollingDualCrc;
+#[cfg(feature = "std")]
+use crate::RollingReader;
+
+// ======================================================================
+// Windows - PUBLIC
+
+/// Iterator over checksums of every window of a slice.
+///
+/// This is created by [`RollingDualCrc::windows`].
+#[derive(Clone, Debug)]
+pub struct Windows<'a> {
+    data: &'a [u8],
+    offset: usize,
+    /// Current window, `None` when iteration is done.
+    crc: Option<RollingDualCrc>,
+}
+
+// ======================================================================
+// Windows - CRATE
+
+impl<'a> Windows<'a> {
+    pub(crate) fn new(data: &'a [u8], window_size: usize) -> Self {
+        if window_size == 0 {
+            panic!("window_size is zero");
+        }
+
+        Self {
+            data,
+            offset: 0,
+            crc: data.get(..window_size).map(RollingDualCrc::new),
+        }
+    }
+}
+
+// ======================================================================
+// Windows - IMPL Iterator
+
+impl<'a> Iterator for Windows<'a> {
+    type Item = (usize, u32, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let crc = self.crc.as_mut()?;
+        let (crc32, crc64) = crc.get();
+        let offset = self.offset;
+
+        match self.data.get(offset + crc.tables().window_size()) {
+            Some(byte) => {
+                crc.roll(*byte);
+                self.offset += 1;
+            }
+            None => self.crc = None,
+        }
+
+        Some((offset, crc32, crc64))
+    }
+}
+
+// ======================================================================
+// ReadWindows - PUBLIC
+
+/// Iterator over checksums of every window of data from [`Read`].
+///
+/// This is created by [`RollingDualCrc::read_windows`]
+/// and is an [`Iterator`] adapter of [`RollingReader`].
+///
+/// On I/O error `Err` is returned and reading can be retried with next call.
+///
+/// [`RollingReader`]: crate::RollingReader
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct ReadWindows<R> {
+    inner: RollingReader<R>,
+}
+
+#[cfg(feature = "std")]
+impl<R> ReadWindows<R> {
+    /// Unwraps this [`ReadWindows`], returning the inner reader.
+    ///
+    /// Any data which has been read but not yet rolled is lost.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+}
+
+// ======================================================================
+// ReadWindows - CRATE
+
+#[cfg(feature = "std")]
+impl<R> ReadWindows<R> {
+    pub(crate) fn new(reader: R, window_size: usize) -> Self {
+        Self {
+            inner: RollingReader::new(reader, window_size),
+        }
+    }
+}
+
+// ======================================================================
+// ReadWindows - IMPL Iterator
+
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for ReadWindows<R> {
+    type Item = io::Result<(u64, u32, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_window().transpose()
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::{test_util::random_data, DualCrc};
+
+    // ============================================================
+    // PANICS
+
+    #[test]
+    #[should_panic]
+    fn zero_window_size() {
+        RollingDualCrc::windows(b"abc", 0);
+    }
+
+    // ============================================================
+    // Windows
+
+    #[test]
+    fn windows_same_as_direct() {
+        let data = random_data(1000);
+        for window_size in [1, 2, 100, 999, 1000] {
+            let windows: Vec<_> = RollingDualCrc::windows(&data, window_size).collect();
+            assert_eq!(windows.len(), data.len() - window_size + 1);
+            for (n, (offset, crc32, crc64)) in windows.into_iter().enumerate() {
+                assert_eq!(offset, n);
+                assert_eq!(
+                    (crc32, crc64),
+                    DualCrc::checksum(&data[offset..offset + window_size])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn windows_shorter_than_window() {
+        assert_eq!(RollingDualCrc::windows(b"", 1).count(), 0);
+        assert_eq!(RollingDualCrc::windows(b"abc", 4).count(), 0);
+    }
+
+    // ============================================================
+    // ReadWindows
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_windows_same_as_windows() {
+        // larger than read buffer to test buffer refilling
+        let data = random_data(150_000);
+        for window_size in [1, 1000, 100_000, 150_000, 150_001] {
+            let mut expected = RollingDualCrc::windows(&data, window_size);
+            for result in RollingDualCrc::read_windows(&data[..], window_size) {
+                let (offset, crc32, crc64) = result.unwrap();
+                assert_eq!(Some((offset as usize, crc32, crc64)), expected.next());
+            }
+            assert!(expected.next().is_none());
+        }
+    }
+}
@@ -1,13 +1,14 @@
-use crate::tables;
+use crate::{
+    catalogue::{Crc32Iscsi, Crc64XzAlgo},
+    tables, CrcAlgorithm,
+};
 
 // ======================================================================
 // CONST - PRIVATE
 
-// CRC-32C (Castagnoli)
-const POLYNOMIAL_32: u32 = 0x1EDC6F41;
+const POLYNOMIAL_32: u32 = Crc32Iscsi::PARAMS.poly as u32;
 
-// CRC-64/XZ
-const POLYNOMIAL_64: u64 = 0x42F0E1EBA9EA3693;
+const POLYNOMIAL_64: u64 = Crc64XzAlgo::PARAMS.poly;
 
 // ======================================================================
 // Zeros - PUBLIC
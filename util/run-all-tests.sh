@@ -6,6 +6,12 @@ cargo test
 cargo test --features crc32c
 cargo test --features crc64fast
 cargo test --features crc32c,crc64fast
+cargo test --features parallel
+cargo test --features digest
+cargo test --features cli
+cargo test --no-default-features --lib
+cargo test --no-default-features --features alloc
+cargo test --no-default-features --features digest --lib
 
 echo OK
 echo